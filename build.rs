@@ -1,8 +1,8 @@
-use ejdict_rs_core::{Dictionary, Word};
+use ejdict_rs_core::Dictionary;
 use std::env;
 use std::fs;
 use std::io;
-use std::io::{BufWriter, Write};
+use std::io::{BufReader, BufWriter, Write};
 use std::path::PathBuf;
 use std::str;
 
@@ -13,19 +13,21 @@ fn main() -> io::Result<()> {
     if output_path.exists() && !ejdict_force_update {
         return Ok(());
     }
-    let res = load_ejdict();
-    let words = res.lines().map(Word::parse_line).collect::<Vec<_>>();
-    let dict = Dictionary::new(words);
+    let res = BufReader::new(fs::File::open(ejdict_path())?);
+    let (dict, warnings) = Dictionary::from_tsv_reader_lenient(res)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    for warning in warnings {
+        println!("cargo:warning=skipped ejdict line: {}", warning);
+    }
     let json = serde_json::to_string_pretty(&dict).unwrap();
     let mut output = BufWriter::new(fs::File::create(output_path)?);
     output.write_all(json.as_bytes())
 }
 
-fn load_ejdict() -> String {
+fn ejdict_path() -> PathBuf {
     let manifest_dir: &'static str = env!("CARGO_MANIFEST_DIR");
-    let ejdict_local_path = PathBuf::new()
+    PathBuf::new()
         .join(&manifest_dir)
         .join("res")
-        .join("ejdic-hand-utf8.txt");
-    fs::read_to_string(&ejdict_local_path).unwrap()
+        .join("ejdic-hand-utf8.txt")
 }
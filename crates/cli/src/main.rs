@@ -2,7 +2,7 @@ use clap::{
     app_from_crate, crate_authors, crate_description, crate_name, crate_version, App, Arg,
     SubCommand,
 };
-use ejdict_rs::{Result, SearchMode, Word};
+use ejdict_rs::{Error, ErrorKind, Result, SearchMode, Word};
 use prettytable::{Cell, Row, Table};
 use std::process;
 use std::str::FromStr;
@@ -14,6 +14,12 @@ fn main() {
         Ok(_) => process::exit(0),
         Err(err) => {
             eprintln!("{}", err);
+            if let ErrorKind::NotFound { en } = err.kind() {
+                let suggestions = ejdict_rs::suggest(en).unwrap_or_default();
+                if !suggestions.is_empty() {
+                    eprintln!("Did you mean: {}", suggestions.join(", "));
+                }
+            }
             process::exit(1);
         }
     }
@@ -22,7 +28,8 @@ fn main() {
 fn run(matches: clap::ArgMatches) -> Result<()> {
     let mut table = Table::new();
     if let Some(look_matches) = matches.subcommand_matches("look") {
-        let word = look_subcommand(look_matches)?;
+        let word = look_subcommand(look_matches)
+            .or_else(|err| print_not_found_json(err, look_matches.is_present("json")))?;
         if look_matches.is_present("json") {
             let json = serde_json::to_string_pretty(&word)?;
             println!("{}", json);
@@ -113,10 +120,14 @@ fn look_subcommand<'a>(matches: &'a clap::ArgMatches) -> Result<&'a Word> {
         .value_of("mode")
         .map(|mode| SearchMode::from_str(mode).unwrap_or(SearchMode::Lower))
         .unwrap();
-    ejdict_rs::look(en, mode)
+    let variant = ejdict_rs::look_variant(en, mode)?;
+    if let Some(spelling) = variant.spelling() {
+        eprintln!("Showing results for: {}", spelling);
+    }
+    Ok(variant.word())
 }
 
-fn candidate_subcommand(matches: &clap::ArgMatches) -> Result<impl Iterator<Item = Word>> {
+fn candidate_subcommand(matches: &clap::ArgMatches) -> Result<impl Iterator<Item = &'static Word>> {
     let en = matches.value_of("en_word").unwrap();
     let mode = matches
         .value_of("mode")
@@ -130,6 +141,21 @@ fn candidate_subcommand(matches: &clap::ArgMatches) -> Result<impl Iterator<Item
     ejdict_rs::candidates(en, mode).and_then(|candidates| Ok(candidates.take(number)))
 }
 
+fn print_not_found_json<T>(err: Error, json: bool) -> Result<T> {
+    if let ErrorKind::NotFound { en } = err.kind() {
+        if json {
+            let suggestions = ejdict_rs::suggest(en)?;
+            let output = serde_json::json!({
+                "error": err.to_string(),
+                "en": en,
+                "suggestions": suggestions,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+    Err(err)
+}
+
 fn table_set_header(table: &mut Table) {
     table.add_row(Row::new(vec![Cell::new("word"), Cell::new("mean")]));
 }
@@ -137,9 +163,9 @@ fn table_set_header(table: &mut Table) {
 fn table_set_row(table: &mut Table, word: &Word) {
     let words: String = word.words().join(",");
     let means: String = word
-        .mean()
-        .split("/")
-        .map(|mean| mean.trim())
+        .senses()
+        .iter()
+        .map(|sense| sense.to_string())
         .collect::<Vec<_>>()
         .join("\n");
     table.add_row(Row::new(vec![Cell::new(&words), Cell::new(&means)]));
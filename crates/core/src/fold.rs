@@ -0,0 +1,109 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Full case foldings that differ from `char::to_lowercase`.
+/// These are all the `C` and `F` mappings of CaseFolding.txt (Unicode 14) that
+/// `to_lowercase` doesn't give, except Cherokee, which is handled by `fold_cherokee`.
+const FOLDINGS: &[(char, &str)] = &[
+    ('µ', "μ"),
+    ('ß', "ss"),
+    ('ŉ', "ʼn"),
+    ('ſ', "s"),
+    ('\u{0345}', "ι"),
+    ('ς', "σ"),
+    ('ϐ', "β"),
+    ('ϑ', "θ"),
+    ('ϕ', "φ"),
+    ('ϖ', "π"),
+    ('ϰ', "κ"),
+    ('ϱ', "ρ"),
+    ('ϵ', "ε"),
+    ('և', "եւ"),
+    ('ᲀ', "в"),
+    ('ᲁ', "д"),
+    ('ᲂ', "о"),
+    ('ᲃ', "с"),
+    ('ᲄ', "т"),
+    ('ᲅ', "т"),
+    ('ᲆ', "ъ"),
+    ('ᲇ', "ѣ"),
+    ('ᲈ', "ꙋ"),
+    ('ẚ', "aʾ"),
+    ('ẞ', "ss"),
+    ('ﬀ', "ff"),
+    ('ﬁ', "fi"),
+    ('ﬂ', "fl"),
+    ('ﬃ', "ffi"),
+    ('ﬄ', "ffl"),
+    ('ﬅ', "st"),
+    ('ﬆ', "st"),
+    ('ﬓ', "մն"),
+    ('ﬔ', "մե"),
+    ('ﬕ', "մի"),
+    ('ﬖ', "վն"),
+    ('ﬗ', "մխ"),
+];
+
+/// Fold English text for case- and diacritic-insensitive matching.
+///
+/// - Letters are full case folded as in CaseFolding.txt, so `Straße` and `STRASSE` are the same.
+/// - Diacritics are removed after canonical decomposition, so `café` and `cafe` are the same.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::fold_en;
+///
+/// assert_eq!(fold_en("Café"), "cafe");
+/// assert_eq!(fold_en("naïve"), fold_en("NAIVE"));
+/// ```
+pub fn fold_en(src: &str) -> String {
+    let mut folded = String::with_capacity(src.len());
+    for c in src.nfd() {
+        for lower in c.to_lowercase() {
+            match FOLDINGS.iter().find(|(from, _)| *from == lower) {
+                Some((_, to)) => folded.push_str(to),
+                None => folded.push(fold_cherokee(lower)),
+            }
+        }
+    }
+    // Marks are removed after folding, since the ypogegrammeni of `ᾳ` is folded to `ι`,
+    // and `İ` is lowercased to `i` followed by a combining dot.
+    folded.retain(|c| !is_combining_mark(c));
+    folded
+}
+
+/// Cherokee letters are folded to the uppercase letters, unlike the other scripts.
+fn fold_cherokee(c: char) -> char {
+    let offset = match c {
+        '\u{ab70}'..='\u{abbf}' => 0xab70 - 0x13a0,
+        '\u{13f8}'..='\u{13fd}' => 0x13f8 - 0x13f0,
+        _ => return c,
+    };
+    char::from_u32(c as u32 - offset).unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fold::fold_en;
+
+    #[test]
+    fn test_fold_en() {
+        assert_eq!(fold_en("café"), "cafe");
+        assert_eq!(fold_en("NAÏVE"), "naive");
+        assert_eq!(fold_en("Straße"), "strasse");
+        assert_eq!(fold_en("ﬁancé"), "fiance");
+        assert_eq!(fold_en("İstanbul"), "istanbul");
+        assert_eq!(fold_en("Ἀθῆναι"), "αθηναι");
+        assert_eq!(fold_en("apple butter"), "apple butter");
+        assert_eq!(fold_en("ẞ"), "ss");
+        assert_eq!(fold_en("ᾳ"), "αι");
+        assert_eq!(fold_en("µ"), fold_en("Μ"));
+        assert_eq!(fold_en("ΣΟΦΟΣ"), fold_en("σοφος"));
+        assert_eq!(fold_en("ﬆ"), "st");
+        assert_eq!(fold_en("և"), "եւ");
+        assert_eq!(fold_en("ᲀ"), "в");
+        assert_eq!(fold_en("ꭰ"), fold_en("Ꭰ"));
+        assert_eq!(fold_en("Ᏸ"), fold_en("ᏸ"));
+    }
+}
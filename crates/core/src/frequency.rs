@@ -0,0 +1,147 @@
+use failure::Fail;
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+/// Word frequencies used to rank and filter candidates.
+/// Words are not case sensitive, so `Apple` and `apple` share a count.
+/// This library ships no frequency data, so load a frequency list or count a corpus.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::FrequencyTable;
+///
+/// # fn main() -> Result<(), ejdict_rs_core::FrequencyError> {
+/// let table = FrequencyTable::from_tsv("the\t5000\napple\t120\n".as_bytes())?;
+/// assert_eq!(table.get("Apple"), Some(120));
+///
+/// let table = FrequencyTable::from_corpus("An apple a day. An apple!".as_bytes())?;
+/// assert_eq!(table.get("apple"), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrequencyTable {
+    counts: HashMap<String, u64>,
+}
+
+impl FrequencyTable {
+    /// Constructor for an empty table.
+    pub fn new() -> Self {
+        FrequencyTable::default()
+    }
+
+    /// Load a frequency list with a word and its count on each line, separated by a tab.
+    /// Empty lines and lines starting with `#` are skipped.
+    /// Counts of the same word are added up.
+    pub fn from_tsv<R: BufRead>(reader: R) -> Result<Self, FrequencyError> {
+        let mut table = FrequencyTable::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut columns = line.split('\t');
+            let word = columns.next().unwrap_or_default().trim();
+            let count = columns.next().and_then(|count| count.trim().parse().ok());
+            match count {
+                Some(count) if !word.is_empty() => table.insert(word, count),
+                _ => {
+                    return Err(FrequencyError::InvalidLine {
+                        line: number + 1,
+                        content: line.clone(),
+                    })
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Count the words of a corpus.
+    /// A word is a run of letters, which may contain an apostrophe or a hyphen, such as `don't`.
+    pub fn from_corpus<R: BufRead>(reader: R) -> Result<Self, FrequencyError> {
+        let mut table = FrequencyTable::new();
+        for line in reader.lines() {
+            for word in tokenize(&line?) {
+                table.insert(word, 1);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Add the count to the word.
+    pub fn insert(&mut self, word: &str, count: u64) {
+        *self.counts.entry(word.to_lowercase()).or_insert(0) += count;
+    }
+
+    /// Get the count of the word.
+    pub fn get(&self, word: &str) -> Option<u64> {
+        self.counts.get(&word.to_lowercase()).copied()
+    }
+
+    /// Get the number of words in the table.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Checks if the table has no words.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// Split the text into words, trimming apostrophes and hyphens at both ends.
+fn tokenize(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphabetic() || c == '\'' || c == '-'))
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '-'))
+        .filter(|word| !word.is_empty())
+}
+
+/// An error returned when a frequency list can't be loaded.
+#[derive(Debug, Fail)]
+pub enum FrequencyError {
+    #[fail(display = "IO error: {}", _0)]
+    Io(io::Error),
+
+    #[fail(
+        display = "Invalid frequency list: line {} isn't a word and a count. line: {}",
+        line, content
+    )]
+    InvalidLine { line: usize, content: String },
+}
+
+impl From<io::Error> for FrequencyError {
+    fn from(err: io::Error) -> Self {
+        FrequencyError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frequency::{FrequencyError, FrequencyTable};
+
+    #[test]
+    fn test_frequency_table_from_tsv() {
+        let src = "# word\tcount\nthe\t5000\n\nApple\t100\napple\t20\n";
+        let table = FrequencyTable::from_tsv(src.as_bytes()).unwrap();
+        assert_eq!(table.get("the"), Some(5000));
+        assert_eq!(table.get("apple"), Some(120));
+        assert_eq!(table.get("blue"), None);
+        assert_eq!(table.len(), 2);
+        match FrequencyTable::from_tsv("the\t5000\napple\tmany\n".as_bytes()) {
+            Err(FrequencyError::InvalidLine { line, .. }) => assert_eq!(line, 2),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_frequency_table_from_corpus() {
+        let src = "Don't eat the apple-green apple.\n'The' apple, 3 times!";
+        let table = FrequencyTable::from_corpus(src.as_bytes()).unwrap();
+        assert_eq!(table.get("apple"), Some(2));
+        assert_eq!(table.get("apple-green"), Some(1));
+        assert_eq!(table.get("the"), Some(2));
+        assert_eq!(table.get("don't"), Some(1));
+        assert_eq!(table.get("3"), None);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::fold::fold_en;
+use crate::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Headword index of a dictionary.
+/// Each key points to the position of the first word that holds it,
+/// so lookups return the same word as a scan in file order.
+#[derive(Debug, Default)]
+pub(crate) struct Index {
+    exact: HashMap<String, usize>,
+    lower: HashMap<String, usize>,
+    folded: HashMap<String, usize>,
+    sorted: Vec<(String, usize)>,
+    reversed: Vec<(String, usize)>,
+}
+
+impl Index {
+    /// Build the index from all headwords of the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let mut index = Index::default();
+        for (pos, word) in words.iter().enumerate() {
+            for en in word.words() {
+                index.exact.entry(en.to_owned()).or_insert(pos);
+                index.lower.entry(en.to_lowercase()).or_insert(pos);
+                index.folded.entry(fold_en(en)).or_insert(pos);
+                index.sorted.push((en.to_owned(), pos));
+                index.reversed.push((en.chars().rev().collect(), pos));
+            }
+        }
+        index.sorted.sort();
+        index.reversed.sort();
+        index
+    }
+
+    /// Get the position of the word that has exactly the same headword.
+    pub(crate) fn exact(&self, pat: &str) -> Option<usize> {
+        self.exact.get(pat).copied()
+    }
+
+    /// Get the position of the word whose lowercased headword equals the pattern.
+    pub(crate) fn lower(&self, pat: &str) -> Option<usize> {
+        self.lower.get(pat).copied()
+    }
+
+    /// Get the position of the word whose folded headword equals the folded pattern.
+    pub(crate) fn folded(&self, pat: &str) -> Option<usize> {
+        self.folded.get(&fold_en(pat)).copied()
+    }
+
+    /// Get the positions of the words that have a headword starting with the pattern.
+    /// Positions are yielded in lexicographic order of the headwords, without duplicates.
+    pub(crate) fn prefixed(&self, pat: &str) -> Prefixed<'_> {
+        Prefixed::new(&self.sorted, pat.to_owned())
+    }
+
+    /// Get the positions of the words that have a headword ending with the pattern.
+    /// Positions are yielded in lexicographic order of the reversed headwords, without duplicates.
+    pub(crate) fn suffixed(&self, pat: &str) -> Prefixed<'_> {
+        Prefixed::new(&self.reversed, pat.chars().rev().collect())
+    }
+}
+
+/// Iterator over a sorted headword table, limited to a prefix range.
+#[derive(Debug, Clone)]
+pub(crate) struct Prefixed<'a> {
+    iter: std::slice::Iter<'a, (String, usize)>,
+    pat: String,
+    seen: HashSet<usize>,
+}
+
+impl<'a> Prefixed<'a> {
+    fn new(table: &'a [(String, usize)], pat: String) -> Self {
+        let start = table.partition_point(|(en, _)| en.as_str() < pat.as_str());
+        Prefixed {
+            iter: table[start..].iter(),
+            pat,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for Prefixed<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some((en, pos)) = self.iter.next() {
+            if !en.starts_with(self.pat.as_str()) {
+                self.iter = [].iter();
+                return None;
+            }
+            if self.seen.insert(*pos) {
+                return Some(*pos);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,127 @@
+/// Half-width katakana from U+FF61 to U+FF9D, and their full-width forms.
+const HALF_WIDTH: &str = "｡｢｣､･ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝ";
+const FULL_WIDTH: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+/// Hiragana grouped by their vowels, used to expand the long-vowel mark.
+const VOWELS: &[(char, &str)] = &[
+    ('あ', "あかさたなはまやらわがざだばぱぁゃゎ"),
+    ('い', "いきしちにひみりぎじぢびぴぃゐ"),
+    ('う', "うくすつぬふむゆるぐずづぶぷぅゅっゔ"),
+    ('え', "えけせてねへめれげぜでべぺぇゑ"),
+    ('お', "おこそとのほもよろをごぞどぼぽぉょ"),
+];
+
+/// Normalize Japanese text for matching.
+///
+/// - Half-width katakana and full-width ASCII are converted to their usual width.
+/// - Katakana is folded into hiragana, so `りんご`, `リンゴ` and `ﾘﾝｺﾞ` are the same.
+/// - The long-vowel mark `ー` is expanded to the vowel of the preceding kana.
+/// - Iteration marks such as `ゝ`, `ゞ` and `々` are expanded to the repeated character.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::normalize_ja;
+///
+/// assert_eq!(normalize_ja("ﾘﾝｺﾞ"), normalize_ja("りんご"));
+/// assert_eq!(normalize_ja("コーヒー"), "こおひい");
+/// ```
+pub fn normalize_ja(src: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(src.len());
+    for c in src.chars() {
+        let c = to_full_width_kana(c);
+        match c {
+            '\u{FF9E}' | '\u{3099}' | '\u{309B}' => match out.last_mut() {
+                Some(last) => *last = voiced(*last),
+                None => out.push(c),
+            },
+            '\u{FF9F}' | '\u{309A}' | '\u{309C}' => match out.last_mut() {
+                Some(last) => *last = semi_voiced(*last),
+                None => out.push(c),
+            },
+            'ー' => {
+                let vowel = out.last().and_then(|&last| vowel_of(last));
+                out.push(vowel.unwrap_or(c));
+            }
+            'ゝ' | 'ヽ' | '々' => {
+                let last = out.last().copied();
+                out.push(last.unwrap_or(c));
+            }
+            'ゞ' | 'ヾ' => {
+                let last = out.last().map(|&last| voiced(last));
+                out.push(last.unwrap_or(c));
+            }
+            _ => out.push(to_hiragana(to_ascii(c))),
+        }
+    }
+    out.into_iter().collect()
+}
+
+fn to_full_width_kana(c: char) -> char {
+    HALF_WIDTH
+        .chars()
+        .position(|half| half == c)
+        .and_then(|i| FULL_WIDTH.chars().nth(i))
+        .unwrap_or(c)
+}
+
+fn to_ascii(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => std::char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
+
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => std::char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn voiced(c: char) -> char {
+    let c = to_hiragana(c);
+    if c == 'う' {
+        return 'ゔ';
+    }
+    if "かきくけこさしすせそたちつてとはひふへほ".contains(c) {
+        return std::char::from_u32(c as u32 + 1).unwrap_or(c);
+    }
+    c
+}
+
+fn semi_voiced(c: char) -> char {
+    let c = to_hiragana(c);
+    if "はひふへほ".contains(c) {
+        return std::char::from_u32(c as u32 + 2).unwrap_or(c);
+    }
+    c
+}
+
+fn vowel_of(c: char) -> Option<char> {
+    VOWELS
+        .iter()
+        .find(|(_, kana)| kana.contains(c))
+        .map(|(vowel, _)| *vowel)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kana::normalize_ja;
+
+    #[test]
+    fn test_normalize_ja() {
+        assert_eq!(normalize_ja("リンゴ"), "りんご");
+        assert_eq!(normalize_ja("ﾘﾝｺﾞ"), "りんご");
+        assert_eq!(normalize_ja("りんご"), "りんご");
+        assert_eq!(normalize_ja("ﾊﾟﾝ"), "ぱん");
+        assert_eq!(normalize_ja("リンコ\u{3099}"), "りんご");
+        assert_eq!(normalize_ja("ＡＢＣ１２３"), "ABC123");
+        assert_eq!(normalize_ja("コーヒー"), normalize_ja("こおひい"));
+        assert_eq!(normalize_ja("ｺｰﾋｰ"), "こおひい");
+        assert_eq!(normalize_ja("いすゞ"), "いすず");
+        assert_eq!(normalize_ja("人々"), "人人");
+        assert_eq!(normalize_ja("ー"), "ー");
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+/// Label of a sense.
+/// This enum is the typed form of the markers ejdict uses, such as 〈U〉 or 《話》.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Label {
+    /// 〈C〉
+    Countable,
+    /// 〈U〉
+    Uncountable,
+    /// Register such as 《話》 or 《詩》.
+    Register(Register),
+    /// Region such as 《米》 or 《英》.
+    Region(Region),
+    /// Subject field such as 《法》 or 《医》.
+    Field(Field),
+    /// Marker that is not known by this library.
+    /// The marker is kept as it is, including the brackets.
+    Raw(String),
+}
+
+impl Label {
+    /// Construct `Label` from the text inside of 〈〉.
+    pub fn from_grammar(src: &str) -> Self {
+        match src.trim() {
+            "C" => Label::Countable,
+            "U" => Label::Uncountable,
+            other => Label::Raw(format!("〈{}〉", other)),
+        }
+    }
+
+    /// Construct `Label` from the text inside of 《》.
+    pub fn from_usage(src: &str) -> Self {
+        let src = src.trim();
+        if let Some(register) = Register::from_marker(src) {
+            return Label::Register(register);
+        }
+        if let Some(region) = Region::from_marker(src) {
+            return Label::Region(region);
+        }
+        if let Some(field) = Field::from_marker(src) {
+            return Label::Field(field);
+        }
+        Label::Raw(format!("《{}》", src))
+    }
+
+    /// Get the marker text used in ejdict, without the brackets.
+    pub fn marker(&self) -> &str {
+        match self {
+            Label::Countable => "C",
+            Label::Uncountable => "U",
+            Label::Register(register) => register.marker(),
+            Label::Region(region) => region.marker(),
+            Label::Field(field) => field.marker(),
+            Label::Raw(raw) => raw
+                .trim_start_matches(&['〈', '《'][..])
+                .trim_end_matches(&['〉', '》'][..]),
+        }
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Label::Countable | Label::Uncountable => write!(f, "〈{}〉", self.marker()),
+            Label::Raw(raw) => write!(f, "{}", raw),
+            _ => write!(f, "《{}》", self.marker()),
+        }
+    }
+}
+
+macro_rules! marker_enum {
+    ($(#[$meta:meta])* $name:ident { $($(#[$vmeta:meta])* $variant:ident => $marker:expr,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+        }
+
+        impl $name {
+            /// Get the variant from the marker text used in ejdict.
+            pub fn from_marker(src: &str) -> Option<Self> {
+                match src {
+                    $($marker => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Get the marker text used in ejdict.
+            pub fn marker(self) -> &'static str {
+                match self {
+                    $($name::$variant => $marker,)*
+                }
+            }
+        }
+    };
+}
+
+marker_enum! {
+    /// Register of a sense.
+    Register {
+        /// 《話》
+        Colloquial => "話",
+        /// 《俗》
+        Slang => "俗",
+        /// 《文》
+        Literary => "文",
+        /// 《詩》
+        Poetic => "詩",
+        /// 《古》
+        Archaic => "古",
+        /// 《まれ》
+        Rare => "まれ",
+        /// 《おどけて》
+        Humorous => "おどけて",
+        /// 《軽べつ的に》
+        Derogatory => "軽べつ的に",
+        /// 《婉曲に》
+        Euphemistic => "婉曲に",
+        /// 《幼児語》
+        Childish => "幼児語",
+    }
+}
+
+marker_enum! {
+    /// Region where a sense is used.
+    Region {
+        /// 《米》
+        American => "米",
+        /// 《英》
+        British => "英",
+        /// 《スコット》
+        Scottish => "スコット",
+        /// 《アイル》
+        Irish => "アイル",
+        /// 《豪》
+        Australian => "豪",
+    }
+}
+
+marker_enum! {
+    /// Subject field of a sense.
+    Field {
+        /// 《法》
+        Law => "法",
+        /// 《医》
+        Medicine => "医",
+        /// 《数》
+        Mathematics => "数",
+        /// 《化》
+        Chemistry => "化",
+        /// 《物》
+        Physics => "物",
+        /// 《生》
+        Biology => "生",
+        /// 《植》
+        Botany => "植",
+        /// 《動》
+        Zoology => "動",
+        /// 《楽》
+        Music => "楽",
+        /// 《軍》
+        Military => "軍",
+        /// 《海》
+        Nautical => "海",
+        /// 《宗》
+        Religion => "宗",
+        /// 《文法》
+        Grammar => "文法",
+        /// 《経》
+        Economics => "経",
+        /// 《商》
+        Commerce => "商",
+        /// 《天》
+        Astronomy => "天",
+        /// 《論》
+        Logic => "論",
+        /// 《哲》
+        Philosophy => "哲",
+        /// 《電算》
+        Computing => "電算",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::label::{Field, Label, Region, Register};
+
+    #[test]
+    fn test_label_parse() {
+        assert_eq!(Label::from_grammar("U"), Label::Uncountable);
+        assert_eq!(Label::from_grammar("C"), Label::Countable);
+        assert_eq!(
+            Label::from_usage("話"),
+            Label::Register(Register::Colloquial)
+        );
+        assert_eq!(Label::from_usage("米"), Label::Region(Region::American));
+        assert_eq!(Label::from_usage("法"), Label::Field(Field::Law));
+        assert_eq!(
+            Label::from_usage("ときに単数扱い"),
+            Label::Raw("《ときに単数扱い》".to_string())
+        );
+        assert_eq!(Label::from_grammar("自").marker(), "自");
+    }
+
+    #[test]
+    fn test_label_display() {
+        assert_eq!(Label::Uncountable.to_string(), "〈U〉");
+        assert_eq!(Label::Register(Register::Poetic).to_string(), "《詩》");
+        assert_eq!(Label::from_grammar("自").to_string(), "〈自〉");
+        assert_eq!(Label::from_usage("まれに").to_string(), "《まれに》");
+    }
+}
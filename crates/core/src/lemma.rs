@@ -0,0 +1,204 @@
+use crate::redirect::RedirectKind;
+use crate::Word;
+use std::collections::HashMap;
+
+/// Word found through its lemma.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lemma<'a> {
+    lemma: &'a str,
+    word: &'a Word,
+}
+
+impl<'a> Lemma<'a> {
+    pub(crate) fn new(lemma: &'a str, word: &'a Word) -> Self {
+        Lemma { lemma, word }
+    }
+
+    /// Get the headword that matched as the lemma.
+    pub fn lemma(&self) -> &'a str {
+        self.lemma
+    }
+
+    /// Get the word of the lemma.
+    pub fn word(&self) -> &'a Word {
+        self.word
+    }
+}
+
+/// Lemmatizer for English words.
+/// Irregular forms are mined from the stub entries of ejdict such as `runの過去形`,
+/// and the other forms are handled by suffix rules.
+#[derive(Debug, Default)]
+pub(crate) struct Lemmatizer {
+    irregular: HashMap<String, Vec<String>>,
+}
+
+impl Lemmatizer {
+    /// Build the irregular form table from the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let mut irregular: HashMap<String, Vec<String>> = HashMap::new();
+        for word in words {
+            let redirect = match word.redirect() {
+                Some(redirect) => redirect,
+                None => continue,
+            };
+            if let RedirectKind::Inflection(_) = redirect.kind() {
+                let target = redirect.target().to_lowercase();
+                for en in word.words() {
+                    let lemmas = irregular.entry(en.to_lowercase()).or_default();
+                    if !lemmas.contains(&target) {
+                        lemmas.push(target.clone());
+                    }
+                }
+            }
+        }
+        Lemmatizer { irregular }
+    }
+
+    /// Get lemma candidates of the lowercased form, the most likely first.
+    /// Candidates are not checked whether they are in the dictionary.
+    pub(crate) fn lemmas(&self, form: &str) -> Vec<String> {
+        let mut lemmas = self.irregular.get(form).cloned().unwrap_or_default();
+        for lemma in regular_lemmas(form) {
+            if !lemmas.contains(&lemma) {
+                lemmas.push(lemma);
+            }
+        }
+        lemmas
+    }
+}
+
+/// Suffix rules as (suffix, replacement).
+/// Rules are tried in order and the longer suffix comes first,
+/// except that `s` comes before `es` so that `uses` is `use` rather than `us`.
+const SUFFIX_RULES: &[(&str, &str)] = &[
+    ("ies", "y"),
+    ("ied", "y"),
+    ("iest", "y"),
+    ("ier", "y"),
+    ("ves", "f"),
+    ("ves", "fe"),
+    ("ing", ""),
+    ("ing", "e"),
+    ("est", ""),
+    ("est", "e"),
+    ("ed", ""),
+    ("ed", "e"),
+    ("er", ""),
+    ("er", "e"),
+    ("s", ""),
+    ("es", ""),
+];
+
+/// Get lemma candidates of the form by suffix rules.
+pub(crate) fn regular_lemmas(form: &str) -> Vec<String> {
+    let mut lemmas: Vec<String> = Vec::new();
+    let mut push = |lemma: String| {
+        if lemma.len() > 1 && !lemmas.contains(&lemma) {
+            lemmas.push(lemma);
+        }
+    };
+    for (suffix, replacement) in SUFFIX_RULES {
+        let stem = match form.strip_suffix(suffix) {
+            Some(stem) if !stem.is_empty() => stem,
+            _ => continue,
+        };
+        if *suffix == "s" && stem.ends_with('s') {
+            continue;
+        }
+        // boxes -> box, goes -> go, but tables isn't tabl
+        if *suffix == "es"
+            && !["s", "x", "z", "ch", "sh", "o"]
+                .iter()
+                .any(|end| stem.ends_with(end))
+        {
+            continue;
+        }
+        // hoping -> hope rather than hop
+        if replacement.is_empty() && ["ing", "ed"].contains(suffix) && ends_with_cvc(stem) {
+            push(format!("{}e", stem));
+        }
+        push(format!("{}{}", stem, replacement));
+        // running -> run, stopped -> stop, bigger -> big
+        if replacement.is_empty() && ["ing", "ed", "er", "est"].contains(suffix) {
+            if let Some(undoubled) = undouble(stem) {
+                push(undoubled.to_owned());
+            }
+        }
+    }
+    lemmas
+}
+
+/// Checks if the stem ends with a single vowel and a single consonant, such as `hop`.
+fn ends_with_cvc(stem: &str) -> bool {
+    let is_vowel = |c: char| "aeiou".contains(c);
+    let mut chars = stem.chars().rev();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(last), Some(vowel), before) => {
+            last.is_ascii_alphabetic()
+                && !is_vowel(last)
+                && !"wxy".contains(last)
+                && is_vowel(vowel)
+                && !before.is_some_and(is_vowel)
+        }
+        _ => false,
+    }
+}
+
+/// Remove the doubled final consonant of the stem.
+fn undouble(stem: &str) -> Option<&str> {
+    let mut chars = stem.chars().rev();
+    let last = chars.next()?;
+    let prev = chars.next()?;
+    if last == prev && last.is_ascii_alphabetic() && !"aeiou".contains(last) {
+        Some(&stem[..stem.len() - last.len_utf8()])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lemma::{regular_lemmas, Lemmatizer};
+    use crate::{Dictionary, SearchMode, Word};
+
+    #[test]
+    fn test_regular_lemmas() {
+        assert!(regular_lemmas("apples").contains(&"apple".to_string()));
+        assert!(regular_lemmas("studied").contains(&"study".to_string()));
+        assert!(regular_lemmas("running").contains(&"run".to_string()));
+        assert!(regular_lemmas("making").contains(&"make".to_string()));
+        assert!(regular_lemmas("boxes").contains(&"box".to_string()));
+        assert!(regular_lemmas("knives").contains(&"knife".to_string()));
+        assert!(regular_lemmas("happiest").contains(&"happy".to_string()));
+        assert!(!regular_lemmas("glass").contains(&"glas".to_string()));
+        assert!(!regular_lemmas("tables").contains(&"tabl".to_string()));
+    }
+
+    #[test]
+    fn test_lemma_order() {
+        let words = ["us", "use", "hop", "hope"]
+            .iter()
+            .map(|en| Word::new(vec![en.to_string()], "意味".to_string()))
+            .collect::<Vec<_>>();
+        let dict = Dictionary::new(words);
+        assert_eq!(dict.look_lemma("uses").unwrap().lemma(), "use");
+        assert_eq!(dict.look_lemma("buses"), None);
+        assert_eq!(dict.look_lemma("hoping").unwrap().lemma(), "hope");
+        assert_eq!(dict.look_lemma("hoped").unwrap().lemma(), "hope");
+        assert_eq!(dict.look_lemma("hopping").unwrap().lemma(), "hop");
+        let word = dict.look("uses", SearchMode::Lemma).unwrap();
+        assert_eq!(word.words(), &["use"]);
+    }
+
+    #[test]
+    fn test_lemmatizer_irregular() {
+        let words = vec![
+            Word::new(vec!["went".to_string()], "goの過去形".to_string()),
+            Word::new(vec!["go".to_string()], "『行く』".to_string()),
+        ];
+        let lemmatizer = Lemmatizer::build(&words);
+        assert_eq!(lemmatizer.lemmas("went")[0], "go");
+        assert!(lemmatizer.lemmas("go").is_empty());
+    }
+}
@@ -17,6 +17,15 @@
 //! - serde
 //!   - Apache 2.0, MIT
 //!   - Serialization framework
+//! - serde_json
+//!   - Apache 2.0, MIT
+//!   - Loading serialized dictionaries
+//! - unicode-normalization
+//!   - Apache 2.0, MIT
+//!   - Unicode normalization for `SearchMode::Folded`
+//! - regex (optional, enabled by the `regex` feature)
+//!   - Apache 2.0, MIT
+//!   - Regular expressions for `SearchMode::Regex`
 //!
 //! Thanks for the great crates.
 //!
@@ -27,30 +36,625 @@
 
 use failure::Fail;
 use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::OnceLock;
+
+mod fold;
+mod frequency;
+mod index;
+mod kana;
+mod label;
+mod lemma;
+mod lookup;
+mod loose;
+mod matcher;
+mod pattern;
+mod phonetic;
+mod rank;
+mod redirect;
+mod reverse;
+mod sense;
+mod spelling;
+mod stack;
+mod suffix;
+mod tsv;
+mod typo;
+
+pub use fold::fold_en;
+pub use frequency::{FrequencyError, FrequencyTable};
+use index::{Index, Prefixed};
+pub use kana::normalize_ja;
+pub use label::{Field, Label, Region, Register};
+pub use lemma::Lemma;
+use lemma::Lemmatizer;
+pub use lookup::{Lookup, Words};
+use loose::LooseIndex;
+pub use matcher::Matcher;
+pub use pattern::Pattern;
+pub use phonetic::metaphone;
+use phonetic::PhoneticIndex;
+pub use rank::{MatchKind, Ranked, Score};
+pub use redirect::{Inflection, Redirect, RedirectKind, Resolved};
+#[cfg(feature = "regex")]
+pub use regex::Error as RegexError;
+#[cfg(feature = "regex")]
+use regex::Regex;
+use reverse::ReverseIndex;
+pub use sense::{Gloss, Sense};
+pub use spelling::{spelling_variants, Variant};
+pub use stack::{DictionaryStack, Hit, Hits, Policy};
+use suffix::SuffixArray;
+pub use tsv::{ParseError, ParseReason};
+pub use typo::damerau_levenshtein;
+use typo::BkTree;
+
+/// Maximum number of redirects followed by `Dictionary::resolve`.
+const MAX_REDIRECTS: usize = 8;
+
+/// Maximum distance of `SearchMode::Typo` when it is parsed from `typo`.
+const DEFAULT_TYPO_DISTANCE: usize = 2;
+
+/// Maximum distance between phonetic keys of `SearchMode::Phonetic` candidates.
+const PHONETIC_KEY_DISTANCE: usize = 1;
+
+/// UTF-8 byte order mark, which some editors write at the start of a file.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
 
 /// Dictionary struct
 /// This struct is holds all the words contained in the English-Japanese dictionary.
+/// The headword index is built when the dictionary is constructed or deserialized.
+/// The index over Japanese glosses, the BK-tree for typos, the phonetic keys,
+/// the suffix array for substrings and the loose keys are built on first use.
+/// A `FrequencyTable` can be attached to rank and filter candidates by frequency.
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(from = "DictionaryData")]
 pub struct Dictionary {
     words: Vec<Word>,
+    #[serde(skip)]
+    index: Index,
+    #[serde(skip)]
+    lemmatizer: Lemmatizer,
+    #[serde(skip)]
+    reverse: OnceLock<ReverseIndex>,
+    #[serde(skip)]
+    typos: OnceLock<BkTree>,
+    #[serde(skip)]
+    phonetics: OnceLock<PhoneticIndex>,
+    #[serde(skip)]
+    suffixes: OnceLock<SuffixArray>,
+    #[serde(skip)]
+    loose: OnceLock<LooseIndex>,
+    #[serde(skip)]
+    frequencies: Option<FrequencyTable>,
 }
 
 impl Dictionary {
     /// Constructor for Dictionary struct.
     pub fn new(words: Vec<Word>) -> Self {
-        Dictionary { words }
+        let index = Index::build(&words);
+        let lemmatizer = Lemmatizer::build(&words);
+        Dictionary {
+            words,
+            index,
+            lemmatizer,
+            reverse: OnceLock::new(),
+            typos: OnceLock::new(),
+            phonetics: OnceLock::new(),
+            suffixes: OnceLock::new(),
+            loose: OnceLock::new(),
+            frequencies: None,
+        }
+    }
+
+    /// Load a dictionary from a file of ejdict TSV or serialized JSON, see `from_reader`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let file = File::open(path)?;
+        Dictionary::from_reader(BufReader::new(file))
+    }
+
+    /// Load a dictionary from ejdict TSV or JSON serialized from `Dictionary`.
+    /// The data is JSON if it starts with `{`, ignoring whitespace, and TSV otherwise.
+    /// A leading UTF-8 byte order mark is skipped.
+    pub fn from_reader<R: BufRead>(mut reader: R) -> Result<Self, LoadError> {
+        if reader.fill_buf()?.starts_with(UTF8_BOM) {
+            reader.consume(UTF8_BOM.len());
+        }
+        let json = reader.fill_buf()?.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{');
+        if json {
+            let dict = serde_json::from_reader(reader)?;
+            Ok(dict)
+        } else {
+            Dictionary::from_tsv_reader(reader)
+        }
+    }
+
+    /// Load a dictionary from ejdict TSV, such as `ejdic-hand-utf8.txt`, line by line.
+    /// Empty lines are skipped. The first line that can't be parsed is returned as an error.
+    pub fn from_tsv_reader<R: BufRead>(reader: R) -> Result<Self, LoadError> {
+        let mut words = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                words.push(tsv::parse_line(&line, number + 1)?);
+            }
+        }
+        Ok(Dictionary::new(words))
+    }
+
+    /// Load a dictionary of headwords without means, such as a `Policy::Hide` layer.
+    /// Each line has comma-separated headwords, and anything after a tab is ignored.
+    /// Empty lines are skipped. The first line with an empty headword is returned as an error.
+    pub fn from_headword_reader<R: BufRead>(reader: R) -> Result<Self, LoadError> {
+        let mut words = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            let headwords = line.split('\t').next().unwrap_or_default();
+            let headwords = tsv::parse_headwords(headwords, number + 1)?;
+            words.push(Word::new(headwords, String::new()));
+        }
+        Ok(Dictionary::new(words))
+    }
+
+    /// Load a dictionary from ejdict TSV like `from_tsv_reader`,
+    /// but skip the lines that can't be parsed and return them as warnings.
+    pub fn from_tsv_reader_lenient<R: BufRead>(
+        reader: R,
+    ) -> Result<(Self, Vec<ParseError>), LoadError> {
+        let mut words = Vec::new();
+        let mut warnings = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match tsv::parse_line(&line, number + 1) {
+                Ok(word) => words.push(word),
+                Err(err) => warnings.push(err),
+            }
+        }
+        Ok((Dictionary::new(words), warnings))
+    }
+
+    /// Attach the frequency table used by `ranked`, `frequency` and `is_common`.
+    pub fn set_frequency_table(&mut self, table: FrequencyTable) {
+        self.frequencies = Some(table);
+    }
+
+    /// Get the attached frequency table.
+    pub fn frequency_table(&self) -> Option<&FrequencyTable> {
+        self.frequencies.as_ref()
+    }
+
+    /// Get the highest frequency among the headwords of the word.
+    /// Returns `None` if no frequency table is attached or no headword is in it.
+    pub fn frequency(&self, word: &Word) -> Option<u64> {
+        word.words()
+            .iter()
+            .filter_map(|en| self.headword_frequency(en))
+            .max()
+    }
+
+    /// Checks if the word occurs at least `min_count` times in the attached frequency table.
+    /// Without a frequency table, every word is common.
+    pub fn is_common(&self, word: &Word, min_count: u64) -> bool {
+        match &self.frequencies {
+            Some(_) => self.frequency(word).unwrap_or(0) >= min_count,
+            None => true,
+        }
+    }
+
+    fn headword_frequency(&self, en: &str) -> Option<u64> {
+        self.frequencies.as_ref().and_then(|table| table.get(en))
+    }
+
+    /// Look up words from English-Japanese dictionary.
+    /// The matcher is a `SearchMode` or a custom `Matcher`.
+    /// In `SearchMode::Lower` and `SearchMode::Folded`,
+    /// British and American spelling variants are looked up if the pattern isn't found.
+    /// `SearchMode::Exact` never returns another spelling.
+    pub fn look<M: Matcher>(&self, pat: &str, matcher: M) -> Option<&Word> {
+        matcher.look(self, pat)
+    }
+
+    /// Look up words like `look`, and report the spelling variant that was used, if any.
+    pub fn look_variant(&self, pat: &str, mode: SearchMode) -> Option<Variant<'_>> {
+        if let Some(word) = self.look_as_spelled(pat, mode) {
+            return Some(Variant::new(None, word));
+        }
+        if !mode.has_spelling_variants() {
+            return None;
+        }
+        spelling_variants(pat).into_iter().find_map(|spelling| {
+            self.look_as_spelled(&spelling, mode)
+                .map(|word| Variant::new(Some(spelling), word))
+        })
+    }
+
+    /// Look up words without trying spelling variants.
+    fn look_as_spelled(&self, pat: &str, mode: SearchMode) -> Option<&Word> {
+        match mode {
+            SearchMode::Exact => self.index.exact(pat).map(|pos| &self.words[pos]),
+            SearchMode::Lower => self
+                .index
+                .lower(&pat.to_lowercase())
+                .map(|pos| &self.words[pos]),
+            SearchMode::Folded => self.index.folded(pat).map(|pos| &self.words[pos]),
+            SearchMode::Loose => self
+                .loose_index()
+                .search(pat)
+                .first()
+                .map(|&pos| &self.words[pos]),
+            SearchMode::Fuzzy => self.index.prefixed(pat).min().map(|pos| &self.words[pos]),
+            SearchMode::Suffix => self.index.suffixed(pat).min().map(|pos| &self.words[pos]),
+            SearchMode::Contains => self
+                .contains_positions(pat)
+                .first()
+                .map(|&pos| &self.words[pos]),
+            SearchMode::Lemma => self.look_lemma(pat).map(|lemma| lemma.word()),
+            SearchMode::Typo { max_distance } => self
+                .typo_positions(pat, max_distance)
+                .first()
+                .map(|&pos| &self.words[pos]),
+            SearchMode::Phonetic => self
+                .phonetic_positions(pat, 0)
+                .first()
+                .map(|&pos| &self.words[pos]),
+            SearchMode::Pattern => Pattern::parse(pat).ok().and_then(|pattern| {
+                self.pattern_positions(&pattern)
+                    .first()
+                    .map(|&pos| &self.words[pos])
+            }),
+            #[cfg(feature = "regex")]
+            SearchMode::Regex { target } => Regex::new(pat).ok().and_then(|regex| {
+                self.words
+                    .iter()
+                    .find(|word| word.regex_matched(&regex, target).is_some())
+            }),
+        }
     }
 
     /// Look up words from English-Japanese dictionary.
-    pub fn look(&self, pat: &str, mode: SearchMode) -> Option<&Word> {
-        self.words.iter().find_map(|word| word.matched(pat, &mode))
+    /// Unlike `look`, an invalid query such as a malformed pattern is reported as an error.
+    pub fn try_look(&self, pat: &str, mode: SearchMode) -> Result<Option<&Word>, QueryError> {
+        mode.validate(pat)?;
+        Ok(self.look(pat, mode))
+    }
+
+    /// Look up words by the lemma of an inflected form such as `running` or `studied`.
+    /// If the form itself is in the dictionary and isn't a stub of an inflected form, it is the lemma.
+    pub fn look_lemma(&self, pat: &str) -> Option<Lemma<'_>> {
+        let form = pat.to_lowercase();
+        self.lemma_positions(&form)
+            .first()
+            .map(|&pos| self.lemma_of(pos, &form))
+    }
+
+    /// Get the positions of the words that can be the lemma of the lowercased form.
+    fn lemma_positions(&self, form: &str) -> Vec<usize> {
+        let mut positions = Vec::new();
+        if let Some(pos) = self.index.lower(form) {
+            if !self.words[pos].is_inflection() {
+                positions.push(pos);
+            }
+        }
+        for lemma in self.lemmatizer.lemmas(form) {
+            if let Some(pos) = self.index.lower(&lemma) {
+                if !positions.contains(&pos) {
+                    positions.push(pos);
+                }
+            }
+        }
+        positions
+    }
+
+    /// Construct `Lemma` of the word at the position, which was found by the form.
+    fn lemma_of(&self, pos: usize, form: &str) -> Lemma<'_> {
+        let word = &self.words[pos];
+        let lemmas = self.lemmatizer.lemmas(form);
+        let lemma = word
+            .words()
+            .iter()
+            .map(String::as_str)
+            .find(|en| {
+                let en = en.to_lowercase();
+                en == form || lemmas.contains(&en)
+            })
+            .unwrap_or_default();
+        Lemma::new(lemma, word)
     }
 
     /// Get matching candidate words.
-    pub fn candidates(self, pat: &str, mode: SearchMode) -> Candidates<std::vec::IntoIter<Word>> {
-        let inner_iter = self.into_iter();
-        Candidates::new(inner_iter, pat.to_owned(), mode)
+    /// The matcher is a `SearchMode` or a custom `Matcher`.
+    /// In `SearchMode::Fuzzy`, candidates are yielded in lexicographic order of the headwords.
+    /// In `SearchMode::Suffix`, candidates are yielded in lexicographic order of the reversed headwords.
+    /// In `SearchMode::Typo`, candidates are yielded in order of edit distance.
+    /// In `SearchMode::Phonetic`, candidates are yielded in order of phonetic key similarity.
+    pub fn candidates<M: Matcher>(&self, pat: &str, matcher: M) -> Candidates<'_> {
+        matcher.candidates(self, pat)
+    }
+
+    /// Get matching candidate words in the search mode, using the indexes.
+    pub(crate) fn mode_candidates(&self, pat: &str, mode: SearchMode) -> Candidates<'_> {
+        let source = match mode {
+            SearchMode::Fuzzy => Source::Indexed(self.index.prefixed(pat)),
+            SearchMode::Suffix => Source::Indexed(self.index.suffixed(pat)),
+            SearchMode::Contains => {
+                let positions = self.contains_positions(pat);
+                Source::Positions(positions.into_iter())
+            }
+            SearchMode::Loose => {
+                let positions = self.loose_index().search(pat).to_vec();
+                Source::Positions(positions.into_iter())
+            }
+            SearchMode::Lemma => {
+                let positions = self.lemma_positions(&pat.to_lowercase());
+                Source::Positions(positions.into_iter())
+            }
+            SearchMode::Typo { max_distance } => {
+                let positions = self.typo_positions(pat, max_distance);
+                Source::Positions(positions.into_iter())
+            }
+            SearchMode::Phonetic => {
+                let positions = self.phonetic_positions(pat, PHONETIC_KEY_DISTANCE);
+                Source::Positions(positions.into_iter())
+            }
+            SearchMode::Pattern => {
+                let positions = match Pattern::parse(pat) {
+                    Ok(pattern) => self.pattern_positions(&pattern),
+                    Err(_) => Vec::new(),
+                };
+                Source::Positions(positions.into_iter())
+            }
+            #[cfg(feature = "regex")]
+            SearchMode::Regex { target } => {
+                let positions = match Regex::new(pat) {
+                    Ok(regex) => self.regex_positions(&regex, target),
+                    Err(_) => Vec::new(),
+                };
+                Source::Positions(positions.into_iter())
+            }
+            _ => Source::Scan {
+                iter: self.words.iter().enumerate(),
+                pat: pat.to_owned(),
+                mode,
+            },
+        };
+        Candidates::new(&self.words, source)
+    }
+
+    /// Get the candidate words at the positions.
+    pub(crate) fn positioned(&self, positions: Vec<usize>) -> Candidates<'_> {
+        Candidates::new(&self.words, Source::Positions(positions.into_iter()))
+    }
+
+    /// Get matching candidate words with their scores, the most relevant first.
+    /// Words are ranked by how their headword matches the pattern, exact before case-folded,
+    /// prefix and typo matches, then by the frequency if a frequency table is attached,
+    /// and by single words before phrases and shorter headwords.
+    /// Candidates with the same score keep the order of `candidates`.
+    pub fn ranked<M: Matcher>(&self, pat: &str, matcher: M) -> Ranked<'_> {
+        let scored = self
+            .candidates(pat, matcher)
+            .map(|word| {
+                let score = Score::of(word, pat, |en| self.headword_frequency(en));
+                (word, score)
+            })
+            .collect();
+        Ranked::new(scored)
+    }
+
+    /// Get matching candidate words.
+    /// Unlike `candidates`, an invalid query such as a malformed pattern is reported as an error.
+    pub fn try_candidates(
+        &self,
+        pat: &str,
+        mode: SearchMode,
+    ) -> Result<Candidates<'_>, QueryError> {
+        mode.validate(pat)?;
+        Ok(self.candidates(pat, mode))
+    }
+
+    /// Look up words by a Japanese gloss.
+    /// The word whose primary gloss is the query is preferred.
+    pub fn reverse_look(&self, ja: &str) -> Option<&Word> {
+        self.reverse_index()
+            .search(ja)
+            .first()
+            .map(|&pos| &self.words[pos])
+    }
+
+    /// Get candidate words that have a gloss containing the Japanese query.
+    /// Hiragana and katakana, and full-width and half-width forms are not distinguished.
+    /// Words whose primary gloss (emphasized with 『』) is the query come first.
+    pub fn reverse_candidates(&self, ja: &str) -> Candidates<'_> {
+        let positions = self.reverse_index().search(ja);
+        Candidates::new(&self.words, Source::Positions(positions.into_iter()))
+    }
+
+    /// Get headwords near the pattern, to suggest when a lookup fails.
+    /// The lemma comes first, then words within one edit, words starting with the pattern
+    /// and words within two edits.
+    pub fn suggest(&self, pat: &str, limit: usize) -> Vec<String> {
+        let lower = pat.to_lowercase();
+        let mut suggestions: Vec<String> = Vec::new();
+        let mut push = |en: &str| {
+            if suggestions.len() < limit && en != pat && !suggestions.iter().any(|s| s == en) {
+                suggestions.push(en.to_owned());
+            }
+        };
+        if let Some(lemma) = self.look_lemma(pat) {
+            push(lemma.lemma());
+        }
+        let typos = self.typo_distances(&lower, DEFAULT_TYPO_DISTANCE);
+        for &(pos, _) in typos.iter().filter(|&&(_, dist)| dist <= 1) {
+            push(closest_headword(&self.words[pos], &lower));
+        }
+        for pos in self.index.prefixed(&lower).take(limit) {
+            let word = &self.words[pos];
+            match word.words().iter().find(|en| en.starts_with(&lower)) {
+                Some(en) => push(en),
+                None => push(closest_headword(word, &lower)),
+            }
+        }
+        for &(pos, _) in typos.iter().filter(|&&(_, dist)| dist > 1) {
+            push(closest_headword(&self.words[pos], &lower));
+        }
+        suggestions
+    }
+
+    /// Get the positions of the words within the edit distance, the closest first.
+    fn typo_positions(&self, pat: &str, max_distance: usize) -> Vec<usize> {
+        self.typo_distances(&pat.to_lowercase(), max_distance)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Get the positions and the distances of the words within the edit distance from the lowercased pattern.
+    fn typo_distances(&self, lower: &str, max_distance: usize) -> Vec<(usize, usize)> {
+        self.typos
+            .get_or_init(|| BkTree::build(&self.words))
+            .search(lower, max_distance)
+    }
+
+    /// Get the positions of the words that have a headword containing the pattern.
+    fn contains_positions(&self, pat: &str) -> Vec<usize> {
+        self.suffixes
+            .get_or_init(|| SuffixArray::build(&self.words))
+            .search(pat)
+    }
+
+    /// Get the positions of the words that have a headword matching the glob pattern, in file order.
+    /// Only the headwords starting with the literal prefix of the pattern are checked.
+    fn pattern_positions(&self, pattern: &Pattern) -> Vec<usize> {
+        let prefix = pattern.literal_prefix();
+        let mut positions = self
+            .index
+            .prefixed(&prefix)
+            .filter(|&pos| self.words[pos].words().iter().any(|en| pattern.matches(en)))
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Get the positions of the words whose target matches the regular expression, in file order.
+    #[cfg(feature = "regex")]
+    fn regex_positions(&self, regex: &Regex, target: RegexTarget) -> Vec<usize> {
+        self.words
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| word.regex_matched(regex, target).is_some())
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Get the positions of the words that sound like the pattern.
+    /// The words are ordered by the distance between keys, then by the edit distance of the headwords.
+    fn phonetic_positions(&self, pat: &str, max_distance: usize) -> Vec<usize> {
+        let lower = pat.to_lowercase();
+        let mut found = self
+            .phonetics
+            .get_or_init(|| PhoneticIndex::build(&self.words))
+            .search(pat, max_distance)
+            .into_iter()
+            .map(|(pos, dist)| {
+                let en = closest_headword(&self.words[pos], &lower).to_lowercase();
+                (dist, damerau_levenshtein(&en, &lower), pos)
+            })
+            .collect::<Vec<_>>();
+        found.sort();
+        found.into_iter().map(|(_, _, pos)| pos).collect()
+    }
+
+    fn loose_index(&self) -> &LooseIndex {
+        self.loose.get_or_init(|| LooseIndex::build(&self.words))
+    }
+
+    fn reverse_index(&self) -> &ReverseIndex {
+        self.reverse
+            .get_or_init(|| ReverseIndex::build(&self.words))
+    }
+
+    /// Follow the redirects of the word to the entry it refers to.
+    /// Returns `None` if the word isn't a redirect, the target isn't found or the redirects form a cycle.
+    pub fn resolve(&self, word: &Word) -> Option<&Word> {
+        let mut visited: Vec<String> = Vec::new();
+        let mut redirect = word.redirect()?;
+        loop {
+            let target = redirect.target();
+            if visited.len() >= MAX_REDIRECTS || visited.iter().any(|en| en == target) {
+                return None;
+            }
+            let current = self
+                .look_as_spelled(target, SearchMode::Exact)
+                .or_else(|| self.look_as_spelled(target, SearchMode::Lower))?;
+            visited.push(target.to_owned());
+            match current.redirect() {
+                Some(next) => redirect = next,
+                None => return Some(current),
+            }
+        }
+    }
+
+    /// Look up words and follow the redirect if the word is a stub entry.
+    pub fn look_resolved(&self, pat: &str, mode: SearchMode) -> Option<Resolved<'_>> {
+        let word = self.look(pat, mode)?;
+        let resolved = match self.resolve(word) {
+            Some(entry) => Resolved::new(Some(word), entry),
+            None => Resolved::new(None, word),
+        };
+        Some(resolved)
+    }
+
+    /// Get the position of the word, if it is borrowed from this dictionary.
+    /// Returns `None` for a word of another dictionary or a clone, even if it is equal.
+    /// Prefer the positions carried by `Candidates` where they are available.
+    pub(crate) fn position_of(&self, word: &Word) -> Option<usize> {
+        let size = std::mem::size_of::<Word>();
+        let start = self.words.as_ptr() as usize;
+        let offset = (word as *const Word as usize).checked_sub(start)?;
+        if offset % size != 0 {
+            return None;
+        }
+        let pos = offset / size;
+        self.words
+            .get(pos)
+            .filter(|found| std::ptr::eq(*found, word))
+            .map(|_| pos)
+    }
+
+    /// Get an iterator over all words in the dictionary.
+    pub fn iter(&self) -> std::slice::Iter<'_, Word> {
+        self.words.iter()
+    }
+}
+
+/// Get the headword of the word closest to the lowercased pattern.
+fn closest_headword<'a>(word: &'a Word, lower: &str) -> &'a str {
+    word.words()
+        .iter()
+        .min_by_key(|en| damerau_levenshtein(&en.to_lowercase(), lower))
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+/// Serialized form of `Dictionary`.
+/// Deserializing through this struct lets the index be rebuilt.
+#[derive(Deserialize)]
+struct DictionaryData {
+    words: Vec<Word>,
+}
+
+impl From<DictionaryData> for Dictionary {
+    fn from(data: DictionaryData) -> Self {
+        Dictionary::new(data.words)
     }
 }
 
@@ -63,6 +667,15 @@ impl IntoIterator for Dictionary {
     }
 }
 
+impl<'a> IntoIterator for &'a Dictionary {
+    type Item = &'a Word;
+    type IntoIter = std::slice::Iter<'a, Word>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// This Struct is that holds word translation information.
 /// `words` field is that holds similar English words.
 #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Deserialize, Serialize)]
@@ -77,16 +690,19 @@ impl Word {
         Word { words, mean }
     }
 
-    /// Construct `Word` structure by parsing line string.
+    /// Construct `Word` structure by parsing line string, like `try_parse_line` but leniently.
+    /// Empty headwords and an empty mean are accepted as they are,
+    /// and a line without a tab is headwords with an empty mean.
     pub fn parse_line(line: &str) -> Self {
-        let secs = line.split("\t").collect::<Vec<&str>>();
-        let words = String::from(secs[0]);
-        let mean = String::from(secs[1]);
-        let words = words
-            .split(",")
-            .map(|word| word.to_owned())
-            .collect::<Vec<String>>();
-        Self::new(words, mean)
+        tsv::parse_line_lenient(line)
+    }
+
+    /// Construct `Word` structure by parsing line string,
+    /// which is comma-separated headwords and the mean separated by a tab.
+    /// Unlike `parse_line`, empty headwords and an empty mean are errors.
+    /// The error reports line 1, since only one line is given.
+    pub fn try_parse_line(line: &str) -> Result<Self, ParseError> {
+        tsv::parse_line(line, 1)
     }
 
     /// Get words reference.
@@ -99,12 +715,55 @@ impl Word {
         self.mean.as_str()
     }
 
+    /// Get the redirect if this word is a stub entry such as `=color` or `runの過去形`.
+    pub fn redirect(&self) -> Option<Redirect> {
+        Redirect::parse(self.mean())
+    }
+
+    /// Checks if this word is a stub of an inflected form such as `runの過去形`.
+    pub fn is_inflection(&self) -> bool {
+        match self.redirect() {
+            Some(redirect) => redirect.kind() != &RedirectKind::Synonym,
+            None => false,
+        }
+    }
+
+    /// Parse the mean into senses.
+    /// Each section of the mean separated by `/` is a sense.
+    pub fn senses(&self) -> Vec<Sense> {
+        sense::parse_senses(self.mean())
+    }
+
     /// Checks if this word matches the argument string.
-    pub fn matched(&self, pat: &str, mode: &SearchMode) -> Option<&Word> {
+    /// The matcher is a `SearchMode` or a custom `Matcher`.
+    /// In `SearchMode::Lemma`, irregular forms don't match, see `SearchMode::Lemma`.
+    pub fn matched<M: Matcher + ?Sized>(&self, pat: &str, matcher: &M) -> Option<&Word> {
+        if matcher.matches(self, pat) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if this word matches the argument string in the search mode.
+    pub(crate) fn mode_matched(&self, pat: &str, mode: &SearchMode) -> Option<&Word> {
         match mode {
             SearchMode::Exact => self.exact_matched(pat),
             SearchMode::Fuzzy => self.fuzzy_matched(pat),
             SearchMode::Lower => self.lower_matched(pat),
+            SearchMode::Folded => self.folded_matched(pat),
+            SearchMode::Loose => self.loose_matched(pat),
+            SearchMode::Suffix => self.suffix_matched(pat),
+            SearchMode::Contains => self.contains_matched(pat),
+            SearchMode::Lemma => self.lemma_matched(pat),
+            SearchMode::Typo { max_distance } => self.typo_matched(pat, *max_distance),
+            SearchMode::Phonetic => self.phonetic_matched(pat),
+            SearchMode::Pattern => self.pattern_matched(pat),
+            #[cfg(feature = "regex")]
+            SearchMode::Regex { target } => {
+                let regex = cached_regex(pat).ok()?;
+                self.regex_matched(&regex, *target)
+            }
         }
     }
 
@@ -126,7 +785,60 @@ impl Word {
     }
 
     fn lower_matched(&self, pat: &str) -> Option<&Word> {
-        self.base_matched(|en| en.to_lowercase().eq(pat))
+        let pat = pat.to_lowercase();
+        self.base_matched(|en| en.to_lowercase().eq(&pat))
+    }
+
+    fn folded_matched(&self, pat: &str) -> Option<&Word> {
+        let pat = fold_en(pat);
+        self.base_matched(|en| fold_en(en).eq(&pat))
+    }
+
+    fn loose_matched(&self, pat: &str) -> Option<&Word> {
+        let pat = loose::loose_key(pat);
+        self.base_matched(|en| loose::loose_key(en).eq(&pat))
+    }
+
+    fn suffix_matched(&self, pat: &str) -> Option<&Word> {
+        self.base_matched(|en| en.ends_with(pat))
+    }
+
+    fn contains_matched(&self, pat: &str) -> Option<&Word> {
+        self.base_matched(|en| en.contains(pat))
+    }
+
+    fn typo_matched(&self, pat: &str, max_distance: usize) -> Option<&Word> {
+        let pat = pat.to_lowercase();
+        self.base_matched(|en| damerau_levenshtein(&en.to_lowercase(), &pat) <= max_distance)
+    }
+
+    fn phonetic_matched(&self, pat: &str) -> Option<&Word> {
+        let key = metaphone(pat);
+        self.base_matched(|en| !key.is_empty() && metaphone(en) == key)
+    }
+
+    fn pattern_matched(&self, pat: &str) -> Option<&Word> {
+        let pattern = Pattern::parse(pat).ok()?;
+        self.base_matched(|en| pattern.matches(en))
+    }
+
+    #[cfg(feature = "regex")]
+    fn regex_matched(&self, regex: &Regex, target: RegexTarget) -> Option<&Word> {
+        match target {
+            RegexTarget::Headword => self.base_matched(|en| regex.is_match(en)),
+            RegexTarget::Mean if regex.is_match(self.mean()) => Some(self),
+            RegexTarget::Mean => None,
+        }
+    }
+
+    /// Only suffix rules are applied, since irregular forms need the whole dictionary.
+    fn lemma_matched(&self, pat: &str) -> Option<&Word> {
+        let form = pat.to_lowercase();
+        let lemmas = lemma::regular_lemmas(&form);
+        self.base_matched(|en| {
+            let en = en.to_lowercase();
+            en == form || lemmas.contains(&en)
+        })
     }
 }
 
@@ -137,12 +849,87 @@ impl From<(Vec<String>, String)> for Word {
     }
 }
 
+/// Compile the regular expression, reusing the last one compiled on this thread,
+/// so that matching word by word compiles it once per query.
+#[cfg(feature = "regex")]
+fn cached_regex(pat: &str) -> Result<Regex, RegexError> {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static LAST_REGEX: RefCell<Option<Regex>> = const { RefCell::new(None) };
+    }
+    LAST_REGEX.with(|last| {
+        let mut last = last.borrow_mut();
+        match &*last {
+            Some(regex) if regex.as_str() == pat => Ok(regex.clone()),
+            _ => {
+                let regex = Regex::new(pat)?;
+                *last = Some(regex.clone());
+                Ok(regex)
+            }
+        }
+    })
+}
+
 /// SearchMode specifies how to check if a word is a match.
+/// Variants may be added, and `Regex` exists only with the `regex` feature,
+/// so matches on it need a wildcard arm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum SearchMode {
     Exact,
     Fuzzy,
     Lower,
+    /// Case- and diacritic-insensitive, see `fold_en`.
+    Folded,
+    /// Ignores case, diacritics, hyphens, periods, apostrophes and spaces, so `usa` matches `U.S.A.`.
+    Loose,
+    Suffix,
+    Contains,
+    /// Inflected forms such as `running` or `went` match the lemma.
+    /// Irregular forms are mined from the stub entries of the dictionary,
+    /// so `Word::matched` and `Matcher::matches`, which see a single word, apply only suffix rules.
+    /// `Dictionary::look` and `Dictionary::candidates` handle both.
+    Lemma,
+    Typo {
+        max_distance: usize,
+    },
+    Phonetic,
+    /// Glob pattern such as `?pp*` or `bl[aeiou]e`, see `Pattern`.
+    Pattern,
+    /// Regular expression searched in the target, like grep.
+    /// Use `^` and `$` to match the whole target.
+    #[cfg(feature = "regex")]
+    Regex {
+        target: RegexTarget,
+    },
+}
+
+/// What `SearchMode::Regex` is applied to.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexTarget {
+    /// Each headword of `Word::words`.
+    Headword,
+    /// `Word::mean`.
+    Mean,
+}
+
+impl SearchMode {
+    /// Checks if `Dictionary::look` tries spelling variants in this mode.
+    fn has_spelling_variants(&self) -> bool {
+        matches!(self, SearchMode::Lower | SearchMode::Folded)
+    }
+
+    /// Checks if the query is valid in this mode, such as a well-formed pattern.
+    pub fn validate(&self, pat: &str) -> Result<(), QueryError> {
+        match self {
+            SearchMode::Pattern => Pattern::parse(pat).map(|_| ()),
+            #[cfg(feature = "regex")]
+            SearchMode::Regex { .. } => Regex::new(pat).map(|_| ()).map_err(QueryError::Regex),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl ToString for SearchMode {
@@ -152,6 +939,22 @@ impl ToString for SearchMode {
             Exact => "exact".to_string(),
             Fuzzy => "fuzzy".to_string(),
             Lower => "lower".to_string(),
+            Folded => "folded".to_string(),
+            Loose => "loose".to_string(),
+            Suffix => "suffix".to_string(),
+            Contains => "contains".to_string(),
+            Lemma => "lemma".to_string(),
+            Typo { max_distance } => format!("typo:{}", max_distance),
+            Phonetic => "phonetic".to_string(),
+            Pattern => "pattern".to_string(),
+            #[cfg(feature = "regex")]
+            Regex {
+                target: RegexTarget::Headword,
+            } => "regex".to_string(),
+            #[cfg(feature = "regex")]
+            Regex {
+                target: RegexTarget::Mean,
+            } => "regex:mean".to_string(),
         }
     }
 }
@@ -170,6 +973,41 @@ impl FromStr for SearchMode {
         if Lower.to_string().eq(s) {
             return Ok(Lower);
         }
+        if Folded.to_string().eq(s) {
+            return Ok(Folded);
+        }
+        if Loose.to_string().eq(s) {
+            return Ok(Loose);
+        }
+        if Suffix.to_string().eq(s) {
+            return Ok(Suffix);
+        }
+        if Contains.to_string().eq(s) {
+            return Ok(Contains);
+        }
+        if Lemma.to_string().eq(s) {
+            return Ok(Lemma);
+        }
+        if Phonetic.to_string().eq(s) {
+            return Ok(Phonetic);
+        }
+        if Pattern.to_string().eq(s) {
+            return Ok(Pattern);
+        }
+        #[cfg(feature = "regex")]
+        for target in [RegexTarget::Headword, RegexTarget::Mean] {
+            if (Regex { target }).to_string().eq(s) {
+                return Ok(Regex { target });
+            }
+        }
+        if s == "typo" {
+            return Ok(Typo {
+                max_distance: DEFAULT_TYPO_DISTANCE,
+            });
+        }
+        if let Some(Ok(max_distance)) = s.strip_prefix("typo:").map(str::parse) {
+            return Ok(Typo { max_distance });
+        }
         Err(ConvertError::InvalidSearchModeName {
             argument: s.to_string(),
         })
@@ -180,45 +1018,47 @@ impl FromStr for SearchMode {
 /// This struct is implemented Iterator.
 /// Use the iterator API to check the result.
 #[derive(Debug, Clone)]
-pub struct Candidates<I>
-where
-    I: Iterator<Item = Word> + Clone,
-{
-    inner_iter: I,
-    pat: String,
-    mode: SearchMode,
+pub struct Candidates<'a> {
+    words: &'a [Word],
+    source: Source<'a>,
 }
 
-impl<I> Candidates<I>
-where
-    I: Iterator<Item = Word> + Clone,
-{
-    /// Constructor for Candidates<I> struct
-    fn new(inner_iter: I, pat: String, mode: SearchMode) -> Candidates<I> {
-        Candidates {
-            inner_iter,
-            pat,
-            mode,
+/// Where the candidates come from.
+#[derive(Debug, Clone)]
+enum Source<'a> {
+    Scan {
+        iter: std::iter::Enumerate<std::slice::Iter<'a, Word>>,
+        pat: String,
+        mode: SearchMode,
+    },
+    Indexed(Prefixed<'a>),
+    Positions(std::vec::IntoIter<usize>),
+}
+
+impl<'a> Candidates<'a> {
+    /// Constructor for Candidates struct
+    fn new(words: &'a [Word], source: Source<'a>) -> Candidates<'a> {
+        Candidates { words, source }
+    }
+
+    /// Get the position of the next candidate in the dictionary.
+    pub(crate) fn next_position(&mut self) -> Option<usize> {
+        match &mut self.source {
+            Source::Scan { iter, pat, mode } => iter
+                .find(|(_, word)| word.mode_matched(pat.as_str(), mode).is_some())
+                .map(|(pos, _)| pos),
+            Source::Indexed(positions) => positions.next(),
+            Source::Positions(positions) => positions.next(),
         }
     }
 }
 
-impl<I> Iterator for Candidates<I>
-where
-    I: Iterator<Item = Word> + Clone,
-{
-    type Item = I::Item;
-
-    fn next(&mut self) -> Option<Word> {
-        let pat = self.pat.as_str();
-        let mode = &self.mode;
-        self.inner_iter.find_map(|word| {
-            if word.matched(pat, mode).is_some() {
-                Some(word)
-            } else {
-                None
-            }
-        })
+impl<'a> Iterator for Candidates<'a> {
+    type Item = &'a Word;
+
+    fn next(&mut self) -> Option<&'a Word> {
+        let words = self.words;
+        self.next_position().map(|pos| &words[pos])
     }
 }
 
@@ -232,9 +1072,70 @@ pub enum ConvertError {
     InvalidSearchModeName { argument: String },
 }
 
+/// An error returned when a dictionary can't be loaded.
+#[derive(Debug, Fail)]
+pub enum LoadError {
+    #[fail(display = "IO error: {}", _0)]
+    Io(io::Error),
+
+    #[fail(display = "Parse error: {}", _0)]
+    Parse(ParseError),
+
+    #[fail(display = "JSON error: {}", _0)]
+    Json(serde_json::Error),
+}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(err: ParseError) -> Self {
+        LoadError::Parse(err)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(err: serde_json::Error) -> Self {
+        LoadError::Json(err)
+    }
+}
+
+/// An error returned when a query isn't valid in the search mode.
+#[derive(Debug, Fail, PartialEq)]
+pub enum QueryError {
+    #[fail(
+        display = "Invalid pattern: The character class at {} isn't closed. pattern: {}",
+        position, pattern
+    )]
+    UnclosedClass { pattern: String, position: usize },
+
+    #[fail(
+        display = "Invalid pattern: The character class at {} is empty. pattern: {}",
+        position, pattern
+    )]
+    EmptyClass { pattern: String, position: usize },
+
+    #[fail(
+        display = "Invalid pattern: The range {}-{} is reversed. pattern: {}",
+        start, end, pattern
+    )]
+    InvalidRange {
+        pattern: String,
+        start: char,
+        end: char,
+    },
+
+    #[cfg(feature = "regex")]
+    #[fail(display = "Invalid regex: {}", _0)]
+    Regex(RegexError),
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{ConvertError, Dictionary, SearchMode, Word};
+    use crate::{ConvertError, Dictionary, QueryError, SearchMode, Word};
     use std::str::FromStr;
 
     #[test]
@@ -245,6 +1146,34 @@ mod tests {
         assert_eq!(apple, Some(&word1()));
         let blue = dict.look("blue", SearchMode::Exact);
         assert_eq!(blue, Some(&word4()));
+        let blue = dict.look("blue", SearchMode::Lower);
+        assert_eq!(blue, Some(&word4()));
+        assert_eq!(dict.look("Apple", SearchMode::Exact), None);
+    }
+
+    #[test]
+    fn test_dictionary_look_indexed() {
+        let words = vec![
+            Word::new(
+                vec!["US".to_string(), "U.S.".to_string()],
+                "米国".to_string(),
+            ),
+            Word::new(vec!["us".to_string()], "『私たちを』".to_string()),
+            Word::new(vec!["us".to_string()], "weの目的格".to_string()),
+        ];
+        let dict = Dictionary::new(words.clone());
+        assert_eq!(dict.look("U.S.", SearchMode::Exact), Some(&words[0]));
+        assert_eq!(dict.look("us", SearchMode::Exact), Some(&words[1]));
+        assert_eq!(dict.look("us", SearchMode::Lower), Some(&words[0]));
+        assert_eq!(dict.look("u.s.", SearchMode::Lower), Some(&words[0]));
+    }
+
+    #[test]
+    fn test_dictionary_deserialize_builds_index() {
+        let src = r#"{"words":[{"words":["apple"],"mean":"『リンゴ』;リンゴの木"}]}"#;
+        let dict = serde_json::from_str::<Dictionary>(src).unwrap();
+        assert_eq!(dict.look("apple", SearchMode::Exact), Some(&word1()));
+        assert_eq!(dict.look("apple", SearchMode::Lower), Some(&word1()));
     }
 
     #[test]
@@ -252,10 +1181,374 @@ mod tests {
         let words = get_test_words();
         let dict = Dictionary::new(words);
         let mut apple_candidates = dict.candidates("apple", SearchMode::Fuzzy);
-        assert_eq!(apple_candidates.next(), Some(word1()));
-        assert_eq!(apple_candidates.next(), Some(word2()));
-        assert_eq!(apple_candidates.next(), Some(word3()));
+        assert_eq!(apple_candidates.next(), Some(&word1()));
+        assert_eq!(apple_candidates.next(), Some(&word2()));
+        assert_eq!(apple_candidates.next(), Some(&word3()));
         assert_eq!(apple_candidates.next(), None);
+        let mut blue_candidates = dict.candidates("blue", SearchMode::Exact);
+        assert_eq!(blue_candidates.next(), Some(&word4()));
+        assert_eq!(blue_candidates.next(), None);
+        assert_eq!(dict.iter().count(), 4);
+    }
+
+    #[test]
+    fn test_dictionary_candidates_sorted() {
+        let words = vec![word3(), word4(), word2(), word1()];
+        let dict = Dictionary::new(words);
+        assert_eq!(dict.look("apple", SearchMode::Fuzzy), Some(&word3()));
+        let apple_candidates = dict.candidates("apple", SearchMode::Fuzzy);
+        assert_eq!(
+            apple_candidates.collect::<Vec<_>>(),
+            vec![&word1(), &word2(), &word3()]
+        );
+    }
+
+    #[test]
+    fn test_dictionary_candidates_dedup() {
+        let color = Word::new(
+            vec!["color".to_string(), "colour".to_string()],
+            "『色』".to_string(),
+        );
+        let dict = Dictionary::new(vec![color.clone(), word4()]);
+        let mut candidates = dict.candidates("colo", SearchMode::Fuzzy);
+        assert_eq!(candidates.next(), Some(&color));
+        assert_eq!(candidates.next(), None);
+    }
+
+    #[test]
+    fn test_dictionary_resolve() {
+        let run = Word::new(vec!["run".to_string()], "『走る』".to_string());
+        let ran = Word::new(vec!["ran".to_string()], "runの過去形".to_string());
+        let loop1 = Word::new(vec!["foo".to_string()], "=bar".to_string());
+        let loop2 = Word::new(vec!["bar".to_string()], "=foo".to_string());
+        let dict = Dictionary::new(vec![run.clone(), ran.clone(), loop1.clone(), loop2]);
+        assert_eq!(dict.resolve(&ran), Some(&run));
+        assert_eq!(dict.resolve(&run), None);
+        assert_eq!(dict.resolve(&loop1), None);
+        let resolved = dict.look_resolved("ran", SearchMode::Exact).unwrap();
+        assert_eq!(resolved.stub(), Some(&ran));
+        assert_eq!(resolved.entry(), &run);
+        let resolved = dict.look_resolved("run", SearchMode::Exact).unwrap();
+        assert_eq!(resolved.stub(), None);
+        assert_eq!(resolved.entry(), &run);
+        let resolved = dict.look_resolved("foo", SearchMode::Exact).unwrap();
+        assert_eq!(resolved.entry(), &loop1);
+    }
+
+    #[test]
+    fn test_dictionary_look_lemma() {
+        let go = Word::new(vec!["go".to_string()], "『行く』".to_string());
+        let went = Word::new(vec!["went".to_string()], "goの過去形".to_string());
+        let news = Word::new(vec!["news".to_string()], "『ニュース』".to_string());
+        let new = Word::new(vec!["new".to_string()], "『新しい』".to_string());
+        let dict = Dictionary::new(vec![word1(), go.clone(), went, news.clone(), new]);
+        let lemma = dict.look_lemma("apples").unwrap();
+        assert_eq!(lemma.lemma(), "apple");
+        assert_eq!(lemma.word(), &word1());
+        let lemma = dict.look_lemma("Went").unwrap();
+        assert_eq!(lemma.lemma(), "go");
+        assert_eq!(lemma.word(), &go);
+        assert_eq!(dict.look("news", SearchMode::Lemma), Some(&news));
+        assert_eq!(dict.look("blues", SearchMode::Lemma), None);
+        let mut candidates = dict.candidates("apples", SearchMode::Lemma);
+        assert_eq!(candidates.next(), Some(&word1()));
+        assert_eq!(candidates.next(), None);
+        // A single word knows only suffix rules, not the irregular forms of the dictionary.
+        assert_eq!(dict.look("went", SearchMode::Lemma), Some(&go));
+        assert_eq!(go.matched("went", &SearchMode::Lemma), None);
+        assert_eq!(
+            word1().matched("apples", &SearchMode::Lemma),
+            Some(&word1())
+        );
+    }
+
+    #[test]
+    fn test_dictionary_position_of() {
+        let dict = Dictionary::new(get_test_words());
+        let other = Dictionary::new(get_test_words());
+        let word = dict.look("blue", SearchMode::Exact).unwrap();
+        assert_eq!(dict.position_of(word), Some(3));
+        assert_eq!(other.position_of(word), None);
+        assert_eq!(dict.position_of(&word.clone()), None);
+        let mut candidates = dict.candidates("apple", SearchMode::Fuzzy);
+        assert_eq!(candidates.next_position(), Some(0));
+    }
+
+    #[test]
+    fn test_dictionary_reverse_look() {
+        let dict = Dictionary::new(get_test_words());
+        assert_eq!(dict.reverse_look("リンゴ"), Some(&word1()));
+        assert_eq!(dict.reverse_look("青色"), Some(&word4()));
+        assert_eq!(dict.reverse_look("赤"), None);
+        let candidates = dict.reverse_candidates("リンゴ").collect::<Vec<_>>();
+        assert_eq!(candidates, vec![&word1(), &word2()]);
+    }
+
+    #[test]
+    fn test_dictionary_look_typo() {
+        let dict = Dictionary::new(get_test_words());
+        let mode = SearchMode::Typo { max_distance: 1 };
+        assert_eq!(dict.look("aplpe", mode), Some(&word1()));
+        assert_eq!(dict.look("Bleu", mode), Some(&word4()));
+        assert_eq!(dict.look("orange", mode), None);
+        let blue = Word::new(vec!["blues".to_string()], "ブルース".to_string());
+        let dict = Dictionary::new(vec![blue.clone(), word4()]);
+        let candidates = dict.candidates("bluess", SearchMode::Typo { max_distance: 2 });
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&blue, &word4()]);
+    }
+
+    #[test]
+    fn test_dictionary_suggest() {
+        let dict = Dictionary::new(get_test_words());
+        assert_eq!(dict.suggest("aplpe", 5), vec!["apple".to_string()]);
+        assert_eq!(dict.suggest("Apples", 5), vec!["apple".to_string()]);
+        assert_eq!(
+            dict.suggest("appl", 2),
+            vec!["apple".to_string(), "apple butter".to_string()]
+        );
+        assert_eq!(dict.suggest("blu", 5), vec!["blue".to_string()]);
+        assert!(dict.suggest("orange", 5).is_empty());
+    }
+
+    #[test]
+    fn test_dictionary_look_phonetic() {
+        let knowledge = Word::new(vec!["knowledge".to_string()], "『知識』".to_string());
+        let blues = Word::new(vec!["blues".to_string()], "ブルース".to_string());
+        let mut words = get_test_words();
+        words.push(blues.clone());
+        words.push(knowledge.clone());
+        let dict = Dictionary::new(words);
+        assert_eq!(dict.look("nolij", SearchMode::Phonetic), Some(&knowledge));
+        assert_eq!(dict.look("apel", SearchMode::Phonetic), Some(&word1()));
+        assert_eq!(dict.look("orange", SearchMode::Phonetic), None);
+        let candidates = dict.candidates("bloo", SearchMode::Phonetic);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&word4(), &blues]);
+    }
+
+    #[test]
+    fn test_dictionary_suffix_and_contains() {
+        let dict = Dictionary::new(get_test_words());
+        assert_eq!(dict.look("green", SearchMode::Suffix), Some(&word3()));
+        assert_eq!(dict.look("butter", SearchMode::Contains), Some(&word2()));
+        assert_eq!(dict.look("pink", SearchMode::Contains), None);
+        let candidates = dict.candidates("e", SearchMode::Suffix);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&word1(), &word4()]);
+        let candidates = dict.candidates("le", SearchMode::Contains);
+        assert_eq!(
+            candidates.collect::<Vec<_>>(),
+            vec![&word1(), &word2(), &word3()]
+        );
+    }
+
+    #[test]
+    fn test_dictionary_pattern() {
+        let dict = Dictionary::new(get_test_words());
+        assert_eq!(dict.look("?pple*", SearchMode::Pattern), Some(&word1()));
+        assert_eq!(dict.look("*[!e]", SearchMode::Pattern), Some(&word2()));
+        let candidates = dict.candidates("apple *", SearchMode::Pattern);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&word2(), &word3()]);
+        let candidates = dict.candidates("?????", SearchMode::Pattern);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&word1()]);
+        assert_eq!(dict.look("app[le", SearchMode::Pattern), None);
+        assert_eq!(
+            dict.try_look("app[le", SearchMode::Pattern),
+            Err(QueryError::UnclosedClass {
+                pattern: "app[le".to_string(),
+                position: 3,
+            })
+        );
+        assert!(dict.try_candidates("[z-a]", SearchMode::Pattern).is_err());
+        assert!(dict.try_candidates("app[le", SearchMode::Exact).is_ok());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_dictionary_regex() {
+        use crate::RegexTarget;
+
+        let dict = Dictionary::new(get_test_words());
+        let headword = SearchMode::Regex {
+            target: RegexTarget::Headword,
+        };
+        let mean = SearchMode::Regex {
+            target: RegexTarget::Mean,
+        };
+        assert_eq!(
+            dict.look("^apple (butter|green)$", headword),
+            Some(&word2())
+        );
+        assert_eq!(dict.look("ブルース", mean), Some(&word4()));
+        let candidates = dict.candidates("e$", headword);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&word1(), &word4()]);
+        let scanned = dict
+            .iter()
+            .filter(|word| word.matched("e$", &headword).is_some());
+        assert_eq!(scanned.count(), 2);
+        assert_eq!(word1().matched("(apple", &headword), None);
+        assert_eq!(dict.look("(apple", headword), None);
+        assert!(matches!(
+            dict.try_look("(apple", headword),
+            Err(QueryError::Regex(_))
+        ));
+        assert_eq!(SearchMode::from_str("regex"), Ok(headword));
+        assert_eq!(SearchMode::from_str("regex:mean"), Ok(mean));
+    }
+
+    #[test]
+    fn test_dictionary_lower_and_folded() {
+        let words = vec![
+            Word::new(vec!["café".to_string()], "喫茶店".to_string()),
+            Word::new(vec!["naïve".to_string()], "素朴な".to_string()),
+        ];
+        let dict = Dictionary::new(words.clone());
+        assert_eq!(dict.look("CAFÉ", SearchMode::Lower), Some(&words[0]));
+        assert_eq!(dict.look("cafe", SearchMode::Lower), None);
+        assert_eq!(dict.look("Cafe", SearchMode::Folded), Some(&words[0]));
+        assert_eq!(dict.look("naive", SearchMode::Folded), Some(&words[1]));
+        let candidates = dict.candidates("NAIVE", SearchMode::Folded);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&words[1]]);
+    }
+
+    #[test]
+    fn test_dictionary_loose() {
+        let dict = Dictionary::new(get_test_words());
+        assert_eq!(dict.look("applebutter", SearchMode::Loose), Some(&word2()));
+        assert_eq!(dict.look("apple-green", SearchMode::Loose), Some(&word3()));
+        assert_eq!(dict.look("applejam", SearchMode::Loose), None);
+        let candidates = dict.candidates("apple", SearchMode::Loose);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&word1()]);
+    }
+
+    #[test]
+    fn test_dictionary_look_variant() {
+        let words = vec![
+            Word::new(vec!["color".to_string()], "色".to_string()),
+            Word::new(vec!["theatre".to_string()], "劇場".to_string()),
+        ];
+        let dict = Dictionary::new(words.clone());
+        assert_eq!(dict.look("colour", SearchMode::Lower), Some(&words[0]));
+        assert_eq!(dict.look("Theater", SearchMode::Lower), Some(&words[1]));
+        assert_eq!(dict.look("COLOUR", SearchMode::Folded), Some(&words[0]));
+        assert_eq!(dict.look("colour", SearchMode::Exact), None);
+        assert_eq!(dict.look("colour", SearchMode::Fuzzy), None);
+        let variant = dict.look_variant("colour", SearchMode::Lower).unwrap();
+        assert_eq!(variant.spelling(), Some("color"));
+        assert_eq!(variant.word(), &words[0]);
+        let variant = dict.look_variant("color", SearchMode::Exact).unwrap();
+        assert_eq!(variant.spelling(), None);
+    }
+
+    #[test]
+    fn test_dictionary_exact_is_exact() {
+        let words = vec![
+            Word::new(vec!["doctor".to_string()], "医者".to_string()),
+            Word::new(vec!["water".to_string()], "水".to_string()),
+        ];
+        let dict = Dictionary::new(words);
+        for pat in &["doctour", "watre"] {
+            assert_eq!(dict.look(pat, SearchMode::Exact), None);
+            assert_eq!(dict.look(pat, SearchMode::Lower), None);
+            assert_eq!(dict.look_variant(pat, SearchMode::Exact), None);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_ranked() {
+        use crate::MatchKind;
+
+        let words = vec![
+            Word::new(vec!["apple pie".to_string()], "アップルパイ".to_string()),
+            Word::new(vec!["applesauce".to_string()], "リンゴソース".to_string()),
+            Word::new(vec!["Apple".to_string()], "アップル社".to_string()),
+            Word::new(vec!["apple".to_string()], "リンゴ".to_string()),
+        ];
+        let dict = Dictionary::new(words.clone());
+        let ranked = dict.ranked("apple", SearchMode::Lower).collect::<Vec<_>>();
+        assert_eq!(ranked[0].0, &words[3]);
+        assert_eq!(ranked[0].1.kind(), MatchKind::Exact);
+        assert_eq!(ranked[1].0, &words[2]);
+        assert_eq!(ranked[1].1.kind(), MatchKind::Folded);
+        let ranked = dict
+            .ranked("app", SearchMode::Fuzzy)
+            .map(|(word, _)| word)
+            .collect::<Vec<_>>();
+        assert_eq!(ranked, vec![&words[3], &words[1], &words[0]]);
+    }
+
+    #[test]
+    fn test_dictionary_frequency_table() {
+        use crate::FrequencyTable;
+
+        let mut dict = Dictionary::new(get_test_words());
+        assert!(dict.is_common(&word2(), 10));
+        let table = FrequencyTable::from_tsv("apple green\t50\napple butter\t5\n".as_bytes());
+        dict.set_frequency_table(table.unwrap());
+        assert_eq!(dict.frequency(&word3()), Some(50));
+        assert_eq!(dict.frequency(&word1()), None);
+        assert!(dict.is_common(&word3(), 10));
+        assert!(!dict.is_common(&word2(), 10));
+        let ranked = dict
+            .ranked("apple ", SearchMode::Fuzzy)
+            .map(|(word, _)| word)
+            .collect::<Vec<_>>();
+        assert_eq!(ranked, vec![&word3(), &word2()]);
+    }
+
+    #[test]
+    fn test_dictionary_from_tsv_reader() {
+        use crate::{LoadError, ParseReason};
+
+        let src = "apple\t『リンゴ』;リンゴの木\n\nblue\t『青い』\n";
+        let dict = Dictionary::from_tsv_reader(src.as_bytes()).unwrap();
+        assert_eq!(dict.iter().count(), 2);
+        assert!(dict.look("blue", SearchMode::Exact).is_some());
+
+        let src = "apple\t『リンゴ』;リンゴの木\nbroken line\nblue\t『青い』\n,green\t緑\n";
+        match Dictionary::from_tsv_reader(src.as_bytes()) {
+            Err(LoadError::Parse(err)) => {
+                assert_eq!((err.line(), err.column()), (2, 12));
+                assert_eq!(err.reason(), &ParseReason::MissingTab);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let (dict, warnings) = Dictionary::from_tsv_reader_lenient(src.as_bytes()).unwrap();
+        assert_eq!(dict.iter().count(), 2);
+        let lines = warnings.iter().map(|err| err.line()).collect::<Vec<_>>();
+        assert_eq!(lines, vec![2, 4]);
+        assert!(Word::try_parse_line("apple").is_err());
+        let word = Word::parse_line("a,,b\t");
+        assert_eq!(word.words(), &["a", "", "b"]);
+        assert_eq!(word.mean(), "");
+        assert_eq!(Word::parse_line("apple").mean(), "");
+        let line = "a\tb\tc\r\n";
+        assert_eq!(Word::parse_line(line), Word::try_parse_line(line).unwrap());
+    }
+
+    #[test]
+    fn test_dictionary_from_reader() {
+        use crate::LoadError;
+
+        let tsv = "apple\t『リンゴ』;リンゴの木\nblue\t『青い』\n";
+        let dict = Dictionary::from_reader(tsv.as_bytes()).unwrap();
+        assert_eq!(dict.iter().count(), 2);
+        let json = serde_json::to_string_pretty(&dict).unwrap();
+        let dict = Dictionary::from_reader(format!("\n  {}", json).as_bytes()).unwrap();
+        assert_eq!(
+            dict.look("blue", SearchMode::Exact).unwrap().mean(),
+            "『青い』"
+        );
+        let dict = Dictionary::from_reader(format!("\u{feff}{}", json).as_bytes()).unwrap();
+        assert_eq!(dict.iter().count(), 2);
+        let dict = Dictionary::from_reader(format!("\u{feff}{}", tsv).as_bytes()).unwrap();
+        assert!(dict.look("apple", SearchMode::Exact).is_some());
+        match Dictionary::from_reader("{\"words\": [".as_bytes()) {
+            Err(LoadError::Json(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match Dictionary::from_path("no/such/ejdict.txt") {
+            Err(LoadError::Io(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
     }
 
     #[test]
@@ -269,10 +1562,29 @@ mod tests {
         let apple = Word::parse_line("apple\t『リンゴ』;リンゴの木");
         assert_eq!(apple.matched("apple", &SearchMode::Exact), Some(&word1()));
         assert_eq!(apple.matched("a", &SearchMode::Fuzzy), Some(&word1()));
-        assert_eq!(apple.matched("Apple", &SearchMode::Lower), None);
+        assert_eq!(apple.matched("Apple", &SearchMode::Lower), Some(&word1()));
+        assert_eq!(apple.matched("ÁPPLE", &SearchMode::Folded), Some(&word1()));
+        assert_eq!(apple.matched("ap-ple", &SearchMode::Loose), Some(&word1()));
         assert_eq!(apple.matched("blue", &SearchMode::Exact), None);
         assert_eq!(apple.matched("a", &SearchMode::Exact), None);
         assert_eq!(apple.matched("Apple", &SearchMode::Exact), None);
+        assert_eq!(apple.matched("Apples", &SearchMode::Lemma), Some(&word1()));
+        let typo = SearchMode::Typo { max_distance: 1 };
+        assert_eq!(apple.matched("aplpe", &typo), Some(&word1()));
+        assert_eq!(apple.matched("alppe", &typo), None);
+        assert_eq!(apple.matched("apel", &SearchMode::Phonetic), Some(&word1()));
+        assert_eq!(apple.matched("ple", &SearchMode::Suffix), Some(&word1()));
+        assert_eq!(apple.matched("ppl", &SearchMode::Contains), Some(&word1()));
+    }
+
+    #[test]
+    fn test_word_senses() {
+        let senses = word4().senses();
+        assert_eq!(senses.len(), 9);
+        assert_eq!(senses[2].usage_labels(), &["話"]);
+        assert_eq!(senses[5].forms(), &["the~"]);
+        assert_eq!(senses[5].usage_labels(), &["詩"]);
+        assert_eq!(senses[8].glosses()[0].text(), "…'を'青色にする");
     }
 
     #[test]
@@ -280,6 +1592,22 @@ mod tests {
         assert_eq!(SearchMode::from_str("exact"), Ok(SearchMode::Exact));
         assert_eq!(SearchMode::from_str("fuzzy"), Ok(SearchMode::Fuzzy));
         assert_eq!(SearchMode::from_str("lower"), Ok(SearchMode::Lower));
+        assert_eq!(SearchMode::from_str("folded"), Ok(SearchMode::Folded));
+        assert_eq!(SearchMode::from_str("loose"), Ok(SearchMode::Loose));
+        assert_eq!(SearchMode::from_str("suffix"), Ok(SearchMode::Suffix));
+        assert_eq!(SearchMode::from_str("contains"), Ok(SearchMode::Contains));
+        assert_eq!(SearchMode::from_str("lemma"), Ok(SearchMode::Lemma));
+        assert_eq!(SearchMode::from_str("pattern"), Ok(SearchMode::Pattern));
+        assert_eq!(
+            SearchMode::from_str("typo"),
+            Ok(SearchMode::Typo { max_distance: 2 })
+        );
+        assert_eq!(
+            SearchMode::from_str("typo:1"),
+            Ok(SearchMode::Typo { max_distance: 1 })
+        );
+        assert!(SearchMode::from_str("typo:x").is_err());
+        assert_eq!(SearchMode::from_str("phonetic"), Ok(SearchMode::Phonetic));
         assert_eq!(
             SearchMode::from_str("other"),
             Result::<SearchMode, ConvertError>::Err(ConvertError::InvalidSearchModeName {
@@ -0,0 +1,143 @@
+use crate::{Dictionary, DictionaryStack, SearchMode, Word};
+
+/// Iterator over borrowed words, returned by `Lookup`.
+pub type Words<'a> = Box<dyn Iterator<Item = &'a Word> + 'a>;
+
+/// Read access to a dictionary backend.
+/// The trait is object safe, so services can take `&dyn Lookup`
+/// and tests can swap in a mock instead of `Dictionary`.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::{Dictionary, Lookup, SearchMode, Word};
+///
+/// fn mean_of(dict: &dyn Lookup, en: &str) -> Option<String> {
+///     dict.look(en, SearchMode::Exact).map(|word| word.mean().to_owned())
+/// }
+///
+/// let words = vec![Word::new(vec!["apple".to_string()], "リンゴ".to_string())];
+/// let dict = Dictionary::new(words);
+/// assert_eq!(mean_of(&dict, "apple"), Some("リンゴ".to_string()));
+/// ```
+pub trait Lookup {
+    /// Look up words from English-Japanese dictionary.
+    fn look(&self, pat: &str, mode: SearchMode) -> Option<&Word>;
+
+    /// Get matching candidate words.
+    fn candidates<'a>(&'a self, pat: &str, mode: SearchMode) -> Words<'a>;
+
+    /// Look up words by a Japanese gloss.
+    fn reverse_look(&self, ja: &str) -> Option<&Word>;
+
+    /// Get candidate words that have a gloss containing the Japanese query.
+    fn reverse_candidates<'a>(&'a self, ja: &str) -> Words<'a>;
+
+    /// Get an iterator over all words.
+    fn words(&self) -> Words<'_>;
+}
+
+impl Lookup for Dictionary {
+    fn look(&self, pat: &str, mode: SearchMode) -> Option<&Word> {
+        Dictionary::look(self, pat, mode)
+    }
+
+    fn candidates<'a>(&'a self, pat: &str, mode: SearchMode) -> Words<'a> {
+        Box::new(Dictionary::candidates(self, pat, mode))
+    }
+
+    fn reverse_look(&self, ja: &str) -> Option<&Word> {
+        Dictionary::reverse_look(self, ja)
+    }
+
+    fn reverse_candidates<'a>(&'a self, ja: &str) -> Words<'a> {
+        Box::new(Dictionary::reverse_candidates(self, ja))
+    }
+
+    fn words(&self) -> Words<'_> {
+        Box::new(self.iter())
+    }
+}
+
+impl Lookup for DictionaryStack {
+    fn look(&self, pat: &str, mode: SearchMode) -> Option<&Word> {
+        DictionaryStack::look(self, pat, mode).map(|hit| hit.word())
+    }
+
+    fn candidates<'a>(&'a self, pat: &str, mode: SearchMode) -> Words<'a> {
+        Box::new(DictionaryStack::candidates(self, pat, mode).map(|hit| hit.word()))
+    }
+
+    fn reverse_look(&self, ja: &str) -> Option<&Word> {
+        DictionaryStack::reverse_look(self, ja).map(|hit| hit.word())
+    }
+
+    fn reverse_candidates<'a>(&'a self, ja: &str) -> Words<'a> {
+        Box::new(DictionaryStack::reverse_candidates(self, ja).map(|hit| hit.word()))
+    }
+
+    fn words(&self) -> Words<'_> {
+        Box::new(self.dictionary().iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lookup::{Lookup, Words};
+    use crate::{Dictionary, DictionaryStack, Policy, SearchMode, Word};
+
+    /// Backend that knows only one word.
+    struct Mock {
+        word: Word,
+    }
+
+    impl Lookup for Mock {
+        fn look(&self, pat: &str, mode: SearchMode) -> Option<&Word> {
+            self.word.matched(pat, &mode)
+        }
+
+        fn candidates<'a>(&'a self, pat: &str, mode: SearchMode) -> Words<'a> {
+            Box::new(self.look(pat, mode).into_iter())
+        }
+
+        fn reverse_look(&self, ja: &str) -> Option<&Word> {
+            Some(&self.word).filter(|word| word.mean().contains(ja))
+        }
+
+        fn reverse_candidates<'a>(&'a self, ja: &str) -> Words<'a> {
+            Box::new(self.reverse_look(ja).into_iter())
+        }
+
+        fn words(&self) -> Words<'_> {
+            Box::new(std::iter::once(&self.word))
+        }
+    }
+
+    fn apple() -> Word {
+        Word::new(vec!["apple".to_string()], "リンゴ".to_string())
+    }
+
+    fn headwords(lookup: &dyn Lookup, pat: &str) -> Vec<String> {
+        lookup
+            .candidates(pat, SearchMode::Fuzzy)
+            .flat_map(|word| word.words().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_lookup_backends() {
+        let blue = Word::new(vec!["blue".to_string()], "青い".to_string());
+        let dict = Dictionary::new(vec![apple(), blue.clone()]);
+        let mut stack = DictionaryStack::new("base", Dictionary::new(vec![apple()]));
+        stack.push("user", Dictionary::new(vec![blue]), Policy::Override);
+        let mock = Mock { word: apple() };
+        let backends: Vec<&dyn Lookup> = vec![&dict, &stack, &mock];
+        for backend in backends {
+            assert_eq!(backend.look("apple", SearchMode::Exact), Some(&apple()));
+            assert_eq!(headwords(backend, "app"), vec!["apple"]);
+            assert_eq!(backend.reverse_look("リンゴ"), Some(&apple()));
+            assert_eq!(backend.reverse_candidates("リンゴ").count(), 1);
+            assert!(backend.words().any(|word| word == &apple()));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{fold_en, Word};
+use std::collections::HashMap;
+
+/// Characters ignored by `SearchMode::Loose`.
+const IGNORED: &[char] = &['-', '.', '\'', '’', ' '];
+
+/// Fold the case and diacritics of the headword with `fold_en`,
+/// and remove hyphens, periods, apostrophes and spaces,
+/// so `e-mail`, `U.S.A.` and `apple butter` become `email`, `usa` and `applebutter`.
+pub(crate) fn loose_key(en: &str) -> String {
+    fold_en(en)
+        .chars()
+        .filter(|c| !IGNORED.contains(c))
+        .collect()
+}
+
+/// Index from the loose keys of all headwords to the positions of the words.
+#[derive(Debug, Default)]
+pub(crate) struct LooseIndex {
+    keys: HashMap<String, Vec<usize>>,
+}
+
+impl LooseIndex {
+    /// Build the index from all headwords of the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let mut index = LooseIndex::default();
+        for (pos, word) in words.iter().enumerate() {
+            for en in word.words() {
+                let positions = index.keys.entry(loose_key(en)).or_default();
+                if positions.last() != Some(&pos) {
+                    positions.push(pos);
+                }
+            }
+        }
+        index
+    }
+
+    /// Get the positions of the words whose loose key equals the loose key of the pattern, in file order.
+    pub(crate) fn search(&self, pat: &str) -> &[usize] {
+        self.keys
+            .get(&loose_key(pat))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loose::{loose_key, LooseIndex};
+    use crate::Word;
+
+    #[test]
+    fn test_loose_index_search() {
+        let words = vec![
+            Word::new(vec!["e-mail".to_string()], "".to_string()),
+            Word::new(
+                vec!["U.S.A.".to_string(), "USA".to_string()],
+                "".to_string(),
+            ),
+            Word::new(vec!["email".to_string()], "".to_string()),
+        ];
+        let index = LooseIndex::build(&words);
+        assert_eq!(index.search("email"), &[0, 2]);
+        assert_eq!(index.search("U.S.A"), &[1]);
+        assert_eq!(index.search("usa"), &[1]);
+        assert_eq!(index.search("Email"), &[0, 2]);
+        assert_eq!(loose_key("Rock 'n' Roll"), "rocknroll");
+    }
+}
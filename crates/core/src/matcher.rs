@@ -0,0 +1,120 @@
+use crate::{Candidates, Dictionary, SearchMode, Word};
+
+/// How to check if a word matches the pattern.
+/// Implement this trait for domain-specific matching, and pass it to `Dictionary::look`
+/// or `Dictionary::candidates` in place of a `SearchMode`.
+/// `SearchMode` is the built-in implementation, which uses the indexes of the dictionary.
+///
+/// Only `matches` is required. By default, `look` and `candidates` scan all words in file order.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::{Dictionary, Matcher, SearchMode, Word};
+///
+/// /// Headwords with the given number of letters, starting with the pattern.
+/// struct Letters(usize);
+///
+/// impl Matcher for Letters {
+///     fn matches(&self, word: &Word, pat: &str) -> bool {
+///         word.words()
+///             .iter()
+///             .any(|en| en.starts_with(pat) && en.chars().count() == self.0)
+///     }
+/// }
+///
+/// let words = vec![
+///     Word::new(vec!["apple".to_string()], "リンゴ".to_string()),
+///     Word::new(vec!["apply".to_string()], "申し込む".to_string()),
+///     Word::new(vec!["applause".to_string()], "拍手".to_string()),
+/// ];
+/// let dict = Dictionary::new(words);
+/// assert_eq!(dict.candidates("app", Letters(5)).count(), 2);
+/// assert_eq!(dict.candidates("app", SearchMode::Fuzzy).count(), 3);
+/// ```
+pub trait Matcher {
+    /// Checks if the word matches the pattern.
+    fn matches(&self, word: &Word, pat: &str) -> bool;
+
+    /// Look up the first word that matches the pattern.
+    fn look<'a>(&self, dict: &'a Dictionary, pat: &str) -> Option<&'a Word> {
+        dict.iter().find(|word| self.matches(word, pat))
+    }
+
+    /// Get the words that match the pattern.
+    fn candidates<'a>(&self, dict: &'a Dictionary, pat: &str) -> Candidates<'a> {
+        let positions = dict
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| self.matches(word, pat))
+            .map(|(pos, _)| pos)
+            .collect();
+        dict.positioned(positions)
+    }
+}
+
+impl Matcher for SearchMode {
+    fn matches(&self, word: &Word, pat: &str) -> bool {
+        word.mode_matched(pat, self).is_some()
+    }
+
+    fn look<'a>(&self, dict: &'a Dictionary, pat: &str) -> Option<&'a Word> {
+        dict.look_variant(pat, *self).map(|variant| variant.word())
+    }
+
+    fn candidates<'a>(&self, dict: &'a Dictionary, pat: &str) -> Candidates<'a> {
+        dict.mode_candidates(pat, *self)
+    }
+}
+
+impl<M: Matcher + ?Sized> Matcher for &M {
+    fn matches(&self, word: &Word, pat: &str) -> bool {
+        (**self).matches(word, pat)
+    }
+
+    fn look<'a>(&self, dict: &'a Dictionary, pat: &str) -> Option<&'a Word> {
+        (**self).look(dict, pat)
+    }
+
+    fn candidates<'a>(&self, dict: &'a Dictionary, pat: &str) -> Candidates<'a> {
+        (**self).candidates(dict, pat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::Matcher;
+    use crate::{Dictionary, SearchMode, Word};
+
+    /// Headwords that read the same backwards.
+    struct Palindrome;
+
+    impl Matcher for Palindrome {
+        fn matches(&self, word: &Word, pat: &str) -> bool {
+            word.words()
+                .iter()
+                .any(|en| en.starts_with(pat) && en.chars().eq(en.chars().rev()))
+        }
+    }
+
+    #[test]
+    fn test_custom_matcher() {
+        let words = vec![
+            Word::new(vec!["level".to_string()], "水平".to_string()),
+            Word::new(vec!["lever".to_string()], "てこ".to_string()),
+            Word::new(vec!["refer".to_string()], "参照する".to_string()),
+        ];
+        let dict = Dictionary::new(words.clone());
+        assert_eq!(dict.look("le", Palindrome), Some(&words[0]));
+        assert_eq!(dict.look("lev", &Palindrome), Some(&words[0]));
+        let candidates = dict.candidates("", Palindrome);
+        assert_eq!(candidates.collect::<Vec<_>>(), vec![&words[0], &words[2]]);
+        let matcher: &dyn Matcher = &Palindrome;
+        assert_eq!(words[2].matched("re", matcher), Some(&words[2]));
+        assert_eq!(words[1].matched("le", matcher), None);
+        assert_eq!(
+            words[1].matched("lever", &SearchMode::Exact),
+            Some(&words[1])
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::QueryError;
+
+/// Glob pattern matched against whole headwords.
+/// `?` matches one character, `*` matches any run of characters
+/// and `[...]` matches one character of the class, such as `[aeiou]`, `[a-z]` or `[!aeiou]`.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::Pattern;
+///
+/// # fn main() -> Result<(), ejdict_rs_core::QueryError> {
+/// let pattern = Pattern::parse("a?pl[aeiou]")?;
+/// assert!(pattern.matches("apple"));
+/// assert!(!pattern.matches("apply"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    Any,
+    Star,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Pattern {
+    /// Parse a glob pattern.
+    /// Returns an error if a character class is unclosed, empty or has a reversed range.
+    pub fn parse(src: &str) -> Result<Self, QueryError> {
+        let chars = src.chars().collect::<Vec<_>>();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '?' => tokens.push(Token::Any),
+                '*' => {
+                    if tokens.last() != Some(&Token::Star) {
+                        tokens.push(Token::Star);
+                    }
+                }
+                '[' => {
+                    let (token, end) = parse_class(src, &chars, i)?;
+                    tokens.push(token);
+                    i = end;
+                }
+                c => tokens.push(Token::Literal(c)),
+            }
+            i += 1;
+        }
+        Ok(Pattern { tokens })
+    }
+
+    /// Get the literal characters before the first wildcard.
+    /// Only headwords starting with this prefix can match.
+    pub(crate) fn literal_prefix(&self) -> String {
+        self.tokens
+            .iter()
+            .map_while(|token| match token {
+                Token::Literal(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Checks if the whole headword matches this pattern.
+    pub fn matches(&self, en: &str) -> bool {
+        let chars = en.chars().collect::<Vec<_>>();
+        let (mut t, mut c) = (0, 0);
+        let mut backtrack: Option<(usize, usize)> = None;
+        while c < chars.len() {
+            match self.tokens.get(t) {
+                Some(Token::Star) => {
+                    backtrack = Some((t, c));
+                    t += 1;
+                    continue;
+                }
+                Some(token) if token.matches(chars[c]) => {
+                    t += 1;
+                    c += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            match backtrack {
+                Some((star, start)) => {
+                    t = star + 1;
+                    c = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            }
+        }
+        self.tokens[t..].iter().all(|token| token == &Token::Star)
+    }
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Literal(literal) => *literal == c,
+            Token::Any => true,
+            Token::Star => false,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|&(start, end)| start <= c && c <= end) != *negated
+            }
+        }
+    }
+}
+
+/// Parse the character class starting at `[`, and return it with the position of `]`.
+fn parse_class(src: &str, chars: &[char], open: usize) -> Result<(Token, usize), QueryError> {
+    let mut i = open + 1;
+    let negated = matches!(chars.get(i), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+    let mut ranges = Vec::new();
+    loop {
+        let c = match chars.get(i) {
+            Some(']') if ranges.is_empty() => {
+                return Err(QueryError::EmptyClass {
+                    pattern: src.to_owned(),
+                    position: open,
+                })
+            }
+            Some(']') => return Ok((Token::Class { negated, ranges }, i)),
+            Some(&c) => c,
+            None => {
+                return Err(QueryError::UnclosedClass {
+                    pattern: src.to_owned(),
+                    position: open,
+                })
+            }
+        };
+        match (chars.get(i + 1), chars.get(i + 2)) {
+            (Some('-'), Some(&end)) if end != ']' => {
+                if end < c {
+                    return Err(QueryError::InvalidRange {
+                        pattern: src.to_owned(),
+                        start: c,
+                        end,
+                    });
+                }
+                ranges.push((c, end));
+                i += 3;
+            }
+            _ => {
+                ranges.push((c, c));
+                i += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pattern::Pattern;
+    use crate::QueryError;
+
+    #[test]
+    fn test_pattern_matches() {
+        let pattern = Pattern::parse("?pp*").unwrap();
+        assert!(pattern.matches("apple"));
+        assert!(pattern.matches("apple butter"));
+        assert!(!pattern.matches("pple"));
+        let pattern = Pattern::parse("*[aeiou]e").unwrap();
+        assert!(pattern.matches("blue"));
+        assert!(!pattern.matches("apple"));
+        let pattern = Pattern::parse("bl[!a-t]e").unwrap();
+        assert!(pattern.matches("blue"));
+        assert!(!pattern.matches("blae"));
+        let pattern = Pattern::parse("a*e*r").unwrap();
+        assert!(pattern.matches("apple butter"));
+        assert!(!pattern.matches("apple green"));
+        assert_eq!(Pattern::parse("app*").unwrap().literal_prefix(), "app");
+    }
+
+    #[test]
+    fn test_pattern_parse_error() {
+        assert_eq!(
+            Pattern::parse("ap[pl"),
+            Err(QueryError::UnclosedClass {
+                pattern: "ap[pl".to_string(),
+                position: 2,
+            })
+        );
+        assert_eq!(
+            Pattern::parse("ap[]le"),
+            Err(QueryError::EmptyClass {
+                pattern: "ap[]le".to_string(),
+                position: 2,
+            })
+        );
+        assert_eq!(
+            Pattern::parse("[z-a]"),
+            Err(QueryError::InvalidRange {
+                pattern: "[z-a]".to_string(),
+                start: 'z',
+                end: 'a',
+            })
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::typo::BkTree;
+use crate::Word;
+
+/// Get the Metaphone key of an English word.
+/// Words that sound alike have the same key, such as `knowledge` and `nolij`.
+/// Each word of a phrase is encoded separately and joined with a space.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::metaphone;
+///
+/// assert_eq!(metaphone("knowledge"), "NLJ");
+/// assert_eq!(metaphone("nolij"), "NLJ");
+/// ```
+pub fn metaphone(word: &str) -> String {
+    word.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|part| {
+            let letters = part
+                .chars()
+                .filter(char::is_ascii_alphabetic)
+                .map(|c| c.to_ascii_uppercase())
+                .collect::<Vec<_>>();
+            encode(&letters)
+        })
+        .filter(|key| !key.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_vowel(c: Option<char>) -> bool {
+    matches!(c, Some('A') | Some('E') | Some('I') | Some('O') | Some('U'))
+}
+
+fn is_front_vowel(c: Option<char>) -> bool {
+    matches!(c, Some('E') | Some('I') | Some('Y'))
+}
+
+fn encode(word: &[char]) -> String {
+    let at = |i: usize| word.get(i).copied();
+    let mut key = String::new();
+    let start = match (at(0), at(1)) {
+        (Some('A'), Some('E'))
+        | (Some('G'), Some('N'))
+        | (Some('K'), Some('N'))
+        | (Some('P'), Some('N'))
+        | (Some('W'), Some('R')) => 1,
+        (Some('X'), _) => {
+            key.push('S');
+            1
+        }
+        (Some('W'), Some('H')) => {
+            key.push('W');
+            2
+        }
+        _ => 0,
+    };
+    for (i, &c) in word.iter().enumerate().skip(start) {
+        let prev = if i > 0 { at(i - 1) } else { None };
+        let next = at(i + 1);
+        let next2 = at(i + 2);
+        if prev == Some(c) && c != 'C' {
+            continue;
+        }
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == start {
+                    key.push(c);
+                }
+            }
+            'B' => {
+                if !(prev == Some('M') && next.is_none()) {
+                    key.push('B');
+                }
+            }
+            'C' => {
+                if next == Some('I') && next2 == Some('A') {
+                    key.push('X');
+                } else if next == Some('H') {
+                    key.push(if prev == Some('S') { 'K' } else { 'X' });
+                } else if is_front_vowel(next) {
+                    if prev != Some('S') {
+                        key.push('S');
+                    }
+                } else {
+                    key.push('K');
+                }
+            }
+            'D' => {
+                if next == Some('G') && is_front_vowel(next2) {
+                    key.push('J');
+                } else {
+                    key.push('T');
+                }
+            }
+            'G' => {
+                let silent_gh = next == Some('H') && next2.is_some() && !is_vowel(next2);
+                let silent_gn = next == Some('N')
+                    && (next2.is_none()
+                        || (next2 == Some('E') && at(i + 3) == Some('D') && at(i + 4).is_none()));
+                let dge = prev == Some('D') && is_front_vowel(next);
+                if silent_gh || silent_gn || dge {
+                    continue;
+                }
+                if is_front_vowel(next) && prev != Some('G') {
+                    key.push('J');
+                } else {
+                    key.push('K');
+                }
+            }
+            'H' => {
+                let after_modifier = matches!(
+                    prev,
+                    Some('C') | Some('S') | Some('P') | Some('T') | Some('G')
+                );
+                if !after_modifier && (!is_vowel(prev) || is_vowel(next)) {
+                    key.push('H');
+                }
+            }
+            'K' => {
+                if prev != Some('C') {
+                    key.push('K');
+                }
+            }
+            'P' => key.push(if next == Some('H') { 'F' } else { 'P' }),
+            'Q' => key.push('K'),
+            'S' => {
+                if next == Some('H')
+                    || (next == Some('I') && matches!(next2, Some('O') | Some('A')))
+                {
+                    key.push('X');
+                } else {
+                    key.push('S');
+                }
+            }
+            'T' => {
+                if next == Some('I') && matches!(next2, Some('O') | Some('A')) {
+                    key.push('X');
+                } else if next == Some('H') {
+                    key.push('0');
+                } else if !(next == Some('C') && next2 == Some('H')) {
+                    key.push('T');
+                }
+            }
+            'V' => key.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(next) {
+                    key.push(c);
+                }
+            }
+            'X' => key.push_str("KS"),
+            'Z' => key.push('S'),
+            _ => key.push(c),
+        }
+    }
+    key
+}
+
+/// Phonetic keys of all headwords.
+/// Keys are held in a BK-tree, so similar keys are found too.
+#[derive(Debug, Default)]
+pub(crate) struct PhoneticIndex {
+    keys: BkTree,
+}
+
+impl PhoneticIndex {
+    /// Build the index from all headwords of the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let keys = words.iter().enumerate().flat_map(|(pos, word)| {
+            word.words()
+                .iter()
+                .map(move |en| (metaphone(en), pos))
+                .filter(|(key, _)| !key.is_empty())
+        });
+        PhoneticIndex {
+            keys: BkTree::from_keys(keys),
+        }
+    }
+
+    /// Get the positions and key distances of the words that sound like the pattern,
+    /// the most similar key first.
+    pub(crate) fn search(&self, pat: &str, max_distance: usize) -> Vec<(usize, usize)> {
+        let key = metaphone(pat);
+        if key.is_empty() {
+            return Vec::new();
+        }
+        self.keys.search(&key, max_distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::phonetic::{metaphone, PhoneticIndex};
+    use crate::Word;
+
+    #[test]
+    fn test_metaphone() {
+        assert_eq!(metaphone("knowledge"), "NLJ");
+        assert_eq!(metaphone("nolij"), "NLJ");
+        assert_eq!(metaphone("phone"), "FN");
+        assert_eq!(metaphone("fone"), "FN");
+        assert_eq!(metaphone("Thomas"), "0MS");
+        assert_eq!(metaphone("school"), "SKL");
+        assert_eq!(metaphone("science"), "SNS");
+        assert_eq!(metaphone("night"), "NT");
+        assert_eq!(metaphone("write"), "RT");
+        assert_eq!(metaphone("apple butter"), "APL BTR");
+        assert_eq!(metaphone(""), "");
+    }
+
+    #[test]
+    fn test_phonetic_index_search() {
+        let words = vec![
+            Word::new(vec!["knowledge".to_string()], "".to_string()),
+            Word::new(vec!["knowledgeable".to_string()], "".to_string()),
+            Word::new(vec!["phone".to_string()], "".to_string()),
+        ];
+        let index = PhoneticIndex::build(&words);
+        assert_eq!(index.search("nolij", 0), vec![(0, 0)]);
+        assert_eq!(index.search("fown", 1), vec![(2, 0)]);
+        assert!(index.search("", 1).is_empty());
+    }
+}
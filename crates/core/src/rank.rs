@@ -0,0 +1,205 @@
+use crate::fold::fold_en;
+use crate::typo::damerau_levenshtein;
+use crate::{Word, DEFAULT_TYPO_DISTANCE};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// How a headword matches the query, the better first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MatchKind {
+    /// The headword is the query.
+    Exact,
+    /// The headword is the query ignoring case and diacritics.
+    Folded,
+    /// The headword starts with the query, ignoring case and diacritics.
+    Prefix,
+    /// The headword is within the edit distance from the query.
+    Typo(usize),
+    /// The headword matches in another way, such as by a pattern or by sound.
+    Other,
+}
+
+impl fmt::Display for MatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchKind::Exact => write!(f, "exact"),
+            MatchKind::Folded => write!(f, "folded"),
+            MatchKind::Prefix => write!(f, "prefix"),
+            MatchKind::Typo(distance) => write!(f, "typo({})", distance),
+            MatchKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// Relevance of a candidate word to the query.
+/// Scores are ordered by the match kind, the frequency (when known, the more frequent first),
+/// single words before phrases, and then the shorter headword first.
+/// The smaller score is the more relevant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Score {
+    kind: MatchKind,
+    frequency: Option<u64>,
+    phrase: bool,
+    length: usize,
+    headword: String,
+}
+
+impl Score {
+    /// Score the word for the query, by its best matching headword.
+    pub(crate) fn of(word: &Word, pat: &str, frequency: impl Fn(&str) -> Option<u64>) -> Self {
+        let folded = fold_en(pat);
+        word.words()
+            .iter()
+            .map(|en| {
+                let kind = match_kind(en, pat, &folded);
+                Score {
+                    kind,
+                    frequency: frequency(en),
+                    phrase: en.split_whitespace().nth(1).is_some(),
+                    length: en.chars().count(),
+                    headword: en.to_owned(),
+                }
+            })
+            .min()
+            .unwrap_or(Score {
+                kind: MatchKind::Other,
+                frequency: None,
+                phrase: false,
+                length: 0,
+                headword: String::new(),
+            })
+    }
+
+    /// Get how the headword matches the query.
+    pub fn kind(&self) -> MatchKind {
+        self.kind
+    }
+
+    /// Get the frequency of the headword, if a frequency table is attached.
+    pub fn frequency(&self) -> Option<u64> {
+        self.frequency
+    }
+
+    /// Checks if the headword is a phrase of several words.
+    pub fn is_phrase(&self) -> bool {
+        self.phrase
+    }
+
+    /// Get the number of characters of the headword.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Get the headword that the score is for.
+    pub fn headword(&self) -> &str {
+        self.headword.as_str()
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind
+            .cmp(&other.kind)
+            .then_with(|| other.frequency.cmp(&self.frequency))
+            .then_with(|| self.phrase.cmp(&other.phrase))
+            .then_with(|| self.length.cmp(&other.length))
+            .then_with(|| self.headword.cmp(&other.headword))
+    }
+}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}, length {}", self.kind, self.headword, self.length)?;
+        if self.phrase {
+            write!(f, ", phrase")?;
+        }
+        if let Some(frequency) = self.frequency {
+            write!(f, ", frequency {}", frequency)?;
+        }
+        Ok(())
+    }
+}
+
+/// Get how the headword matches the query.
+/// `folded` is the query folded by `fold_en`.
+fn match_kind(en: &str, pat: &str, folded: &str) -> MatchKind {
+    if en == pat {
+        return MatchKind::Exact;
+    }
+    let en = fold_en(en);
+    if en == folded {
+        MatchKind::Folded
+    } else if en.starts_with(folded) {
+        MatchKind::Prefix
+    } else {
+        match damerau_levenshtein(&en, folded) {
+            distance if distance <= DEFAULT_TYPO_DISTANCE => MatchKind::Typo(distance),
+            _ => MatchKind::Other,
+        }
+    }
+}
+
+/// Candidate words with their scores, the most relevant first.
+/// This struct is implemented Iterator.
+#[derive(Debug, Clone)]
+pub struct Ranked<'a> {
+    iter: std::vec::IntoIter<(&'a Word, Score)>,
+}
+
+impl<'a> Ranked<'a> {
+    /// Rank the candidates. Candidates with the same score keep their order.
+    pub(crate) fn new(mut scored: Vec<(&'a Word, Score)>) -> Self {
+        scored.sort_by(|(_, a), (_, b)| a.cmp(b));
+        Ranked {
+            iter: scored.into_iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for Ranked<'a> {
+    type Item = (&'a Word, Score);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rank::{MatchKind, Score};
+    use crate::Word;
+
+    fn score(en: &str, pat: &str) -> Score {
+        let word = Word::new(vec![en.to_string()], "".to_string());
+        Score::of(&word, pat, |_| None)
+    }
+
+    #[test]
+    fn test_score_kind() {
+        assert_eq!(score("apple", "apple").kind(), MatchKind::Exact);
+        assert_eq!(score("Apple", "apple").kind(), MatchKind::Folded);
+        assert_eq!(score("apple butter", "apple").kind(), MatchKind::Prefix);
+        assert_eq!(score("apply", "apple").kind(), MatchKind::Typo(1));
+        assert_eq!(score("blue", "apple").kind(), MatchKind::Other);
+    }
+
+    #[test]
+    fn test_score_order() {
+        assert!(score("apple", "apple") < score("Apple", "apple"));
+        assert!(score("apples", "app") < score("applesauce", "app"));
+        assert!(score("applesauce", "app") < score("apple pie", "app"));
+        let word = Word::new(vec!["applesauce".to_string()], "".to_string());
+        let frequent = Score::of(&word, "app", |_| Some(10));
+        assert!(frequent < score("apples", "app"));
+        assert_eq!(
+            frequent.to_string(),
+            "prefix applesauce, length 10, frequency 10"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::Word;
+
+/// Pointer from a stub entry to another word.
+/// ejdict has entries whose mean is only `=color` or `runの過去形`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Redirect {
+    target: String,
+    kind: RedirectKind,
+}
+
+impl Redirect {
+    /// Construct `Redirect` struct by parsing the mean of a word.
+    /// Returns `None` if the mean is not a pointer to another word.
+    pub fn parse(mean: &str) -> Option<Self> {
+        let mean = mean.trim();
+        if let Some(target) = mean.strip_prefix('=').or_else(|| mean.strip_prefix('＝')) {
+            let target = target.trim();
+            if is_headword(target) {
+                return Some(Redirect {
+                    target: target.to_owned(),
+                    kind: RedirectKind::Synonym,
+                });
+            }
+            return None;
+        }
+        let sep = mean.find('の')?;
+        let target = mean[..sep].trim();
+        if !is_headword(target) {
+            return None;
+        }
+        let inflections = mean[sep + 'の'.len_utf8()..]
+            .split('・')
+            .map(|form| Inflection::from_marker(form.trim()))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Redirect {
+            target: target.to_owned(),
+            kind: RedirectKind::Inflection(inflections),
+        })
+    }
+
+    /// Get target headword reference.
+    pub fn target(&self) -> &str {
+        self.target.as_str()
+    }
+
+    /// Get kind reference.
+    pub fn kind(&self) -> &RedirectKind {
+        &self.kind
+    }
+}
+
+/// Kind of `Redirect`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RedirectKind {
+    /// `=word`
+    Synonym,
+    /// `wordの過去形` and so on.
+    Inflection(Vec<Inflection>),
+}
+
+/// Inflected form that a stub entry refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Inflection {
+    Past,
+    PastParticiple,
+    PresentParticiple,
+    ThirdPersonSingular,
+    Plural,
+    Comparative,
+    Superlative,
+}
+
+impl Inflection {
+    /// Get the variant from the marker text used in ejdict.
+    pub fn from_marker(src: &str) -> Option<Self> {
+        use Inflection::*;
+        match src {
+            "過去" | "過去形" => Some(Past),
+            "過去分詞" | "過去分詞形" => Some(PastParticiple),
+            "現在分詞" | "現在分詞形" | "ing形" => Some(PresentParticiple),
+            "三人称単数現在" | "三単現" => Some(ThirdPersonSingular),
+            "複数" | "複数形" => Some(Plural),
+            "比較級" => Some(Comparative),
+            "最上級" => Some(Superlative),
+            _ => None,
+        }
+    }
+}
+
+/// Resolved result of looking up a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resolved<'a> {
+    stub: Option<&'a Word>,
+    entry: &'a Word,
+}
+
+impl<'a> Resolved<'a> {
+    pub(crate) fn new(stub: Option<&'a Word>, entry: &'a Word) -> Self {
+        Resolved { stub, entry }
+    }
+
+    /// Get the stub entry that was followed, if the word was a redirect.
+    pub fn stub(&self) -> Option<&'a Word> {
+        self.stub
+    }
+
+    /// Get the resolved entry.
+    pub fn entry(&self) -> &'a Word {
+        self.entry
+    }
+}
+
+/// Checks if the string looks like an English headword.
+fn is_headword(src: &str) -> bool {
+    !src.is_empty()
+        && src
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " -'.".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::redirect::{Inflection, Redirect, RedirectKind};
+
+    #[test]
+    fn test_redirect_parse() {
+        let redirect = Redirect::parse("=color").unwrap();
+        assert_eq!(redirect.target(), "color");
+        assert_eq!(redirect.kind(), &RedirectKind::Synonym);
+        let redirect = Redirect::parse("studyの過去形・過去分詞").unwrap();
+        assert_eq!(redirect.target(), "study");
+        assert_eq!(
+            redirect.kind(),
+            &RedirectKind::Inflection(vec![Inflection::Past, Inflection::PastParticiple])
+        );
+        assert_eq!(Redirect::parse("『リンゴ』;リンゴの木"), None);
+        assert_eq!(Redirect::parse("leftの過去 / 『左の』"), None);
+        assert_eq!(Redirect::parse("=『青い』"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::kana::normalize_ja;
+use crate::Word;
+use std::collections::HashMap;
+
+/// Inverted index over the glosses of all words.
+/// Glosses and queries are normalized by `normalize_ja`.
+/// Each character points to the glosses that contain it,
+/// so a query only checks the glosses that have its rarest character.
+#[derive(Debug, Default)]
+pub(crate) struct ReverseIndex {
+    glosses: Vec<IndexedGloss>,
+    postings: HashMap<char, Vec<usize>>,
+}
+
+#[derive(Debug)]
+struct IndexedGloss {
+    text: String,
+    bare: String,
+    pos: usize,
+    primary: bool,
+}
+
+impl ReverseIndex {
+    /// Build the index from the senses of the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let mut index = ReverseIndex::default();
+        for (pos, word) in words.iter().enumerate() {
+            for sense in word.senses() {
+                for gloss in sense.glosses() {
+                    let id = index.glosses.len();
+                    let text = normalize_ja(gloss.text());
+                    for c in text.chars() {
+                        let ids = index.postings.entry(c).or_default();
+                        if ids.last() != Some(&id) {
+                            ids.push(id);
+                        }
+                    }
+                    index.glosses.push(IndexedGloss {
+                        bare: strip_notes(&text),
+                        text,
+                        pos,
+                        primary: gloss.is_primary(),
+                    });
+                }
+            }
+        }
+        index
+    }
+
+    /// Get the positions of the words that have a gloss containing the query.
+    /// Words whose primary gloss is the query come first, and ties are in file order.
+    pub(crate) fn search(&self, query: &str) -> Vec<usize> {
+        let query = normalize_ja(query.trim());
+        let query = query.as_str();
+        let rarest = query
+            .chars()
+            .map(|c| self.postings.get(&c).map(Vec::as_slice).unwrap_or(&[]))
+            .min_by_key(|ids| ids.len());
+        let ids = match rarest {
+            Some(ids) => ids,
+            None => return Vec::new(),
+        };
+        let mut ranks: HashMap<usize, u8> = HashMap::new();
+        for &id in ids {
+            let gloss = &self.glosses[id];
+            if let Some(rank) = gloss.rank(query) {
+                let best = ranks.entry(gloss.pos).or_insert(rank);
+                *best = (*best).min(rank);
+            }
+        }
+        let mut ranked = ranks.into_iter().collect::<Vec<_>>();
+        ranked.sort_by_key(|&(pos, rank)| (rank, pos));
+        ranked.into_iter().map(|(pos, _)| pos).collect()
+    }
+}
+
+impl IndexedGloss {
+    /// Rank of this gloss for the query, the smaller the better.
+    fn rank(&self, query: &str) -> Option<u8> {
+        let exact = self.bare == query || self.text == query;
+        match (exact, self.primary) {
+            (true, true) => Some(0),
+            (true, false) => Some(1),
+            _ if !self.text.contains(query) => None,
+            (false, true) => Some(2),
+            (false, false) => Some(3),
+        }
+    }
+}
+
+/// Remove parenthetical notes such as `(小さな)` from the gloss.
+fn strip_notes(text: &str) -> String {
+    let mut depth = 0usize;
+    let mut bare = String::new();
+    for c in text.chars() {
+        match c {
+            '(' | '（' => depth += 1,
+            ')' | '）' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => bare.push(c),
+            _ => {}
+        }
+    }
+    bare.trim().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reverse::ReverseIndex;
+    use crate::Word;
+
+    #[test]
+    fn test_reverse_index_search() {
+        let words = vec![
+            Word::new(
+                vec!["apple butter".to_string()],
+                "リンゴジャム(リンゴに香料・砂糖を加えて煮つめたジャム)".to_string(),
+            ),
+            Word::new(vec!["crab apple".to_string()], "(野生の)リンゴ".to_string()),
+            Word::new(
+                vec!["apple".to_string()],
+                "『リンゴ』;リンゴの木".to_string(),
+            ),
+            Word::new(vec!["blue".to_string()], "『青い』".to_string()),
+        ];
+        let index = ReverseIndex::build(&words);
+        assert_eq!(index.search("リンゴ"), vec![2, 1, 0]);
+        assert_eq!(index.search("りんご"), vec![2, 1, 0]);
+        assert_eq!(index.search("ﾘﾝｺﾞ"), vec![2, 1, 0]);
+        assert_eq!(index.search("青"), vec![3]);
+        assert!(index.search("緑").is_empty());
+        assert!(index.search("").is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use crate::label::Label;
+use std::fmt;
+
+/// A sense of a word.
+/// This struct is one of the sections of `Word::mean` separated by `/`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sense {
+    text: String,
+    glosses: Vec<Gloss>,
+    grammar_labels: Vec<String>,
+    usage_labels: Vec<String>,
+    forms: Vec<String>,
+}
+
+impl Sense {
+    /// Construct `Sense` struct by parsing a section of ejdict markup.
+    pub fn parse(src: &str) -> Self {
+        let mut sense = Sense {
+            text: src.trim().to_owned(),
+            ..Sense::default()
+        };
+        let mut gloss = GlossBuilder::default();
+        let mut depth = 0usize;
+        let mut chars = src.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '〈' | '《' if depth == 0 => {
+                    let close = if c == '〈' { '〉' } else { '》' };
+                    let rest = chars.as_str();
+                    match rest.find(close) {
+                        Some(end) => {
+                            let label = rest[..end].trim().to_owned();
+                            chars = rest[end + close.len_utf8()..].chars();
+                            if label.is_empty() {
+                                continue;
+                            }
+                            if c == '〈' {
+                                sense.grammar_labels.push(label);
+                            } else if is_form_note(&label) {
+                                sense.forms.push(label);
+                            } else {
+                                sense.usage_labels.push(label);
+                            }
+                        }
+                        None => gloss.text.push(c),
+                    }
+                }
+                '『' | '』' if depth == 0 => gloss.primary = true,
+                ',' | ';' | '，' | '；' if depth == 0 => {
+                    sense.glosses.extend(gloss.build());
+                    gloss = GlossBuilder::default();
+                }
+                '(' | '（' => {
+                    depth += 1;
+                    gloss.text.push(c);
+                }
+                ')' | '）' => {
+                    depth = depth.saturating_sub(1);
+                    gloss.text.push(c);
+                }
+                _ => gloss.text.push(c),
+            }
+        }
+        sense.glosses.extend(gloss.build());
+        sense
+    }
+
+    /// Get the source text of the section, as it is in ejdict.
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Get glosses reference.
+    pub fn glosses(&self) -> &[Gloss] {
+        self.glosses.as_ref()
+    }
+
+    /// Get primary glosses, which are emphasized with 『』 in ejdict.
+    pub fn primary_glosses(&self) -> impl Iterator<Item = &Gloss> {
+        self.glosses.iter().filter(|gloss| gloss.is_primary())
+    }
+
+    /// Get grammatical labels such as `U` of 〈U〉.
+    pub fn grammar_labels(&self) -> &[String] {
+        self.grammar_labels.as_ref()
+    }
+
+    /// Get usage labels such as `話` of 《話》.
+    pub fn usage_labels(&self) -> &[String] {
+        self.usage_labels.as_ref()
+    }
+
+    /// Get form notes such as `the blues` of 《the blues》.
+    pub fn forms(&self) -> &[String] {
+        self.forms.as_ref()
+    }
+
+    /// Get typed labels of both grammatical and usage labels.
+    pub fn labels(&self) -> Vec<Label> {
+        let grammar = self.grammar_labels.iter().map(|l| Label::from_grammar(l));
+        let usage = self.usage_labels.iter().map(|l| Label::from_usage(l));
+        grammar.chain(usage).collect()
+    }
+
+    /// Checks if this sense has the label.
+    pub fn has_label(&self, label: &Label) -> bool {
+        self.labels().contains(label)
+    }
+
+    /// Checks if this sense has no glosses, labels nor form notes.
+    fn is_empty(&self) -> bool {
+        self.glosses.is_empty()
+            && self.grammar_labels.is_empty()
+            && self.usage_labels.is_empty()
+            && self.forms.is_empty()
+    }
+}
+
+/// Displays the source text, keeping the order of labels and the separators.
+impl fmt::Display for Sense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A translation of a word in a sense.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gloss {
+    text: String,
+    primary: bool,
+}
+
+impl Gloss {
+    /// Construct for `Gloss` struct.
+    pub fn new(text: String, primary: bool) -> Self {
+        Gloss { text, primary }
+    }
+
+    /// Get text reference without the 『』 emphasis.
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Checks if this gloss is emphasized with 『』.
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+}
+
+impl fmt::Display for Gloss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.primary {
+            write!(f, "『{}』", self.text)
+        } else {
+            write!(f, "{}", self.text)
+        }
+    }
+}
+
+#[derive(Default)]
+struct GlossBuilder {
+    text: String,
+    primary: bool,
+}
+
+impl GlossBuilder {
+    fn build(self) -> Option<Gloss> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            None
+        } else {
+            Some(Gloss::new(text.to_owned(), self.primary))
+        }
+    }
+}
+
+/// Parse `Word::mean` into senses.
+/// Sections are separated by `/` outside of parentheses.
+pub(crate) fn parse_senses(mean: &str) -> Vec<Sense> {
+    let mut senses = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in mean.char_indices() {
+        match c {
+            '(' | '（' => depth += 1,
+            ')' | '）' => depth = depth.saturating_sub(1),
+            '/' if depth == 0 => {
+                senses.push(&mean[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    senses.push(&mean[start..]);
+    senses
+        .into_iter()
+        .map(Sense::parse)
+        .filter(|sense| !sense.is_empty())
+        .collect()
+}
+
+/// Checks if the content of 《》 is a form note instead of a usage label.
+/// Form notes contain English, such as `the blues` or `a~`.
+fn is_form_note(label: &str) -> bool {
+    label.chars().any(|c| c.is_ascii_alphabetic() || c == '~')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::label::{Label, Register};
+    use crate::sense::{parse_senses, Gloss, Sense};
+
+    #[test]
+    fn test_sense_parse() {
+        let sense = Sense::parse("〈U〉〈C〉『青色』,あい色;青色の着物");
+        assert_eq!(sense.grammar_labels(), &["U", "C"]);
+        assert_eq!(
+            sense.glosses(),
+            &[
+                Gloss::new("青色".to_string(), true),
+                Gloss::new("あい色".to_string(), false),
+                Gloss::new("青色の着物".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            sense.primary_glosses().collect::<Vec<_>>(),
+            vec![&Gloss::new("青色".to_string(), true)]
+        );
+        assert_eq!(sense.to_string(), "〈U〉〈C〉『青色』,あい色;青色の着物");
+    }
+
+    #[test]
+    fn test_sense_parse_labels() {
+        let sense = Sense::parse("《the blues》《話 》気のふさぎ,うれいの色");
+        assert_eq!(sense.forms(), &["the blues"]);
+        assert_eq!(sense.usage_labels(), &["話"]);
+        assert!(sense.has_label(&Label::Register(Register::Colloquial)));
+        assert_eq!(sense.glosses().len(), 2);
+        assert_eq!(
+            sense.to_string(),
+            "《the blues》《話 》気のふさぎ,うれいの色"
+        );
+        let sense = Sense::parse(" 気のふさぎ;《話》うれいの色 ");
+        assert_eq!(sense.usage_labels(), &["話"]);
+        assert_eq!(sense.to_string(), "気のふさぎ;《話》うれいの色");
+        let sense = Sense::parse("《the blues》《ときに単数扱い》(ジャズ音楽の,黒人の)ブルース");
+        assert_eq!(sense.usage_labels(), &["ときに単数扱い"]);
+        assert_eq!(
+            sense.labels(),
+            vec![Label::Raw("《ときに単数扱い》".to_string())]
+        );
+        assert_eq!(
+            sense.glosses(),
+            &[Gloss::new(
+                "(ジャズ音楽の,黒人の)ブルース".to_string(),
+                false
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_senses() {
+        let senses = parse_senses("『青い』,あい色の / 青黒い / 《話》陰気な,憂うつな /");
+        assert_eq!(senses.len(), 3);
+        assert_eq!(senses[0].glosses()[0], Gloss::new("青い".to_string(), true));
+        assert_eq!(
+            senses[1].glosses()[0],
+            Gloss::new("青黒い".to_string(), false)
+        );
+        assert_eq!(senses[2].usage_labels(), &["話"]);
+        assert_eq!(parse_senses("リンゴ(1/2個)").len(), 1);
+    }
+}
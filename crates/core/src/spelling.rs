@@ -0,0 +1,218 @@
+use crate::Word;
+
+/// British and American spellings that the rules don't cover.
+const EXCEPTIONS: &[(&str, &str)] = &[
+    ("aeroplane", "airplane"),
+    ("ageing", "aging"),
+    ("aluminium", "aluminum"),
+    ("analogue", "analog"),
+    ("axe", "ax"),
+    ("cancelled", "canceled"),
+    ("cancelling", "canceling"),
+    ("catalogue", "catalog"),
+    ("cheque", "check"),
+    ("cosy", "cozy"),
+    ("defence", "defense"),
+    ("dialogue", "dialog"),
+    ("doughnut", "donut"),
+    ("draught", "draft"),
+    ("grey", "gray"),
+    ("jewellery", "jewelry"),
+    ("judgement", "judgment"),
+    ("kerb", "curb"),
+    ("licence", "license"),
+    ("manoeuvre", "maneuver"),
+    ("mould", "mold"),
+    ("moustache", "mustache"),
+    ("offence", "offense"),
+    ("plough", "plow"),
+    ("pretence", "pretense"),
+    ("programme", "program"),
+    ("pyjamas", "pajamas"),
+    ("sceptic", "skeptic"),
+    ("travelled", "traveled"),
+    ("traveller", "traveler"),
+    ("travelling", "traveling"),
+    ("tyre", "tire"),
+];
+
+/// Stems spelled with `-our` in British and `-or` in American, such as `col` of `colour`.
+/// Only these stems are swapped, so `doctor` and `error` stay as they are.
+const OUR_STEMS: &[&str] = &[
+    "arb", "ard", "arm", "behavi", "cand", "clam", "col", "demean", "endeav", "fav", "flav",
+    "harb", "hon", "hum", "lab", "neighb", "od", "parl", "ranc", "rig", "rum", "sav", "splend",
+    "tum", "val", "vap", "vig",
+];
+
+/// Endings that may follow `-our` / `-or`, such as `colours` or `honourable`.
+const OUR_ENDINGS: &[&str] = &["", "s", "ed", "ing", "ful", "ite", "ites", "able", "less"];
+
+/// Stems spelled with `-is-` in British and `-iz-` in American, such as `organ` of `organise`.
+/// Only these stems are swapped, so `promise` and `otherwise` stay as they are.
+const ISE_STEMS: &[&str] = &[
+    "apolog",
+    "author",
+    "capital",
+    "categor",
+    "central",
+    "character",
+    "civil",
+    "critic",
+    "emphas",
+    "final",
+    "general",
+    "global",
+    "industrial",
+    "legal",
+    "local",
+    "maxim",
+    "memor",
+    "minim",
+    "modern",
+    "normal",
+    "optim",
+    "organ",
+    "popular",
+    "prior",
+    "real",
+    "recogn",
+    "special",
+    "standard",
+    "summar",
+    "symbol",
+    "sympath",
+    "util",
+    "visual",
+];
+
+/// Endings that may follow `-is-` / `-iz-`, such as `organised` or `organisation`.
+const ISE_ENDINGS: &[&str] = &["e", "es", "ed", "er", "ers", "ing", "ation", "ations"];
+
+/// Stems spelled with `-re` in British and `-er` in American, such as `cent` of `centre`.
+/// Only these stems are swapped, so `water` and `mother` stay as they are.
+const RE_STEMS: &[&str] = &[
+    "calib", "cent", "fib", "goit", "lit", "lust", "meag", "met", "mit", "ochr", "sab", "sepulch",
+    "somb", "spect", "theat",
+];
+
+/// Endings that may follow `-re` / `-er`, such as `theatres`.
+const RE_ENDINGS: &[&str] = &["", "s"];
+
+/// Get the other spellings of a British or American word.
+/// `colour` gives `color`, `organize` gives `organise` and `theatre` gives `theater`.
+/// The variants keep the capitalization of the word.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::spelling_variants;
+///
+/// assert_eq!(spelling_variants("colour"), vec!["color"]);
+/// assert_eq!(spelling_variants("Organize"), vec!["Organise"]);
+/// assert_eq!(spelling_variants("grey"), vec!["gray"]);
+/// ```
+pub fn spelling_variants(en: &str) -> Vec<String> {
+    let lower = en.to_lowercase();
+    let mut variants = Vec::new();
+    let mut push = |variant: String| {
+        if variant != lower && !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    };
+    for &(british, american) in EXCEPTIONS {
+        if lower == british {
+            push(american.to_owned());
+        } else if lower == american {
+            push(british.to_owned());
+        }
+    }
+    let rules = [
+        (OUR_STEMS, OUR_ENDINGS, "our", "or"),
+        (ISE_STEMS, ISE_ENDINGS, "is", "iz"),
+        (RE_STEMS, RE_ENDINGS, "re", "er"),
+    ];
+    for (stems, endings, british, american) in rules {
+        for stem in stems {
+            for ending in endings {
+                if lower == format!("{}{}{}", stem, british, ending) {
+                    push(format!("{}{}{}", stem, american, ending));
+                } else if lower == format!("{}{}{}", stem, american, ending) {
+                    push(format!("{}{}{}", stem, british, ending));
+                }
+            }
+        }
+    }
+    variants
+        .into_iter()
+        .map(|variant| restore_case(en, variant))
+        .collect()
+}
+
+/// Apply the capitalization of the original word to the lowercased variant.
+fn restore_case(original: &str, variant: String) -> String {
+    let mut chars = original.chars().filter(|c| c.is_alphabetic());
+    match chars.next() {
+        Some(first) if first.is_uppercase() && chars.all(char::is_uppercase) => {
+            variant.to_uppercase()
+        }
+        Some(first) if first.is_uppercase() => {
+            let mut variant_chars = variant.chars();
+            match variant_chars.next() {
+                Some(c) => c.to_uppercase().chain(variant_chars).collect(),
+                None => variant,
+            }
+        }
+        _ => variant,
+    }
+}
+
+/// The word found by a lookup, and the spelling variant that found it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant<'a> {
+    spelling: Option<String>,
+    word: &'a Word,
+}
+
+impl<'a> Variant<'a> {
+    pub(crate) fn new(spelling: Option<String>, word: &'a Word) -> Self {
+        Variant { spelling, word }
+    }
+
+    /// Get the spelling variant that was looked up instead of the query.
+    /// Returns `None` if the query was found as it is.
+    pub fn spelling(&self) -> Option<&str> {
+        self.spelling.as_deref()
+    }
+
+    /// Get the word found.
+    pub fn word(&self) -> &'a Word {
+        self.word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spelling::spelling_variants;
+
+    #[test]
+    fn test_spelling_variants() {
+        assert_eq!(spelling_variants("colour"), vec!["color"]);
+        assert_eq!(spelling_variants("color"), vec!["colour"]);
+        assert_eq!(spelling_variants("honourable"), vec!["honorable"]);
+        assert_eq!(spelling_variants("organise"), vec!["organize"]);
+        assert_eq!(spelling_variants("organizations"), vec!["organisations"]);
+        assert_eq!(spelling_variants("theatre"), vec!["theater"]);
+        assert_eq!(spelling_variants("centers"), vec!["centres"]);
+        assert_eq!(spelling_variants("defense"), vec!["defence"]);
+        assert_eq!(spelling_variants("Colour"), vec!["Color"]);
+        assert_eq!(spelling_variants("COLOUR"), vec!["COLOR"]);
+        assert!(spelling_variants("for").is_empty());
+        assert!(spelling_variants("more").is_empty());
+        assert!(spelling_variants("apple").is_empty());
+        assert!(spelling_variants("doctour").is_empty());
+        assert!(spelling_variants("watre").is_empty());
+        assert!(spelling_variants("otherwise").is_empty());
+        assert!(spelling_variants("promise").is_empty());
+        assert!(spelling_variants("error").is_empty());
+    }
+}
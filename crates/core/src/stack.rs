@@ -0,0 +1,406 @@
+use crate::{Candidates, Dictionary, Matcher, Word};
+use std::collections::HashMap;
+
+/// How the entries of a layer are combined with the entries of the layers below,
+/// when they share a headword.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// The entry of this layer replaces the entries below.
+    Override,
+    /// The senses of this layer are appended to the entry below, after `/`.
+    Append,
+    /// The headwords of this layer are removed from the entries below.
+    /// The entry of this layer isn't shown, so its mean can be empty.
+    /// Load a hide layer with `Dictionary::from_headword_reader`.
+    Hide,
+}
+
+/// Several dictionaries layered on top of each other, such as the built-in data and user files.
+/// Layers are merged when they are pushed, so lookups are as fast as on a single `Dictionary`.
+/// Every hit reports the layers its entry came from.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs_core::{Dictionary, DictionaryStack, Policy, SearchMode};
+///
+/// # fn main() -> Result<(), ejdict_rs_core::LoadError> {
+/// let base = Dictionary::from_reader("apple\t『リンゴ』\n".as_bytes())?;
+/// let user = Dictionary::from_reader("apple\tアップル社の製品\n".as_bytes())?;
+/// let mut stack = DictionaryStack::new("ejdict", base);
+/// stack.push("user", user, Policy::Append);
+/// let hit = stack.look("apple", SearchMode::Exact).unwrap();
+/// assert_eq!(hit.word().mean(), "『リンゴ』 / アップル社の製品");
+/// assert_eq!(hit.layers(), &["ejdict", "user"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DictionaryStack {
+    names: Vec<String>,
+    merged: Dictionary,
+    origins: Vec<Vec<usize>>,
+}
+
+impl DictionaryStack {
+    /// Constructor with the bottom layer.
+    pub fn new(name: &str, base: Dictionary) -> Self {
+        let origins = vec![vec![0]; base.iter().len()];
+        DictionaryStack {
+            names: vec![name.to_owned()],
+            merged: base,
+            origins,
+        }
+    }
+
+    /// Push a layer on top of the stack.
+    /// Entries of the layer that share a headword are merged first, as with `Policy::Append`.
+    /// Then each entry is combined by the policy with the entries below, headword by headword.
+    /// The other headwords of an entry below are kept.
+    pub fn push(&mut self, name: &str, dictionary: Dictionary, policy: Policy) {
+        let layer = self.names.len();
+        self.names.push(name.to_owned());
+        let mut merged = std::mem::replace(&mut self.merged, Dictionary::new(Vec::new()));
+        let frequencies = merged.frequencies.take();
+        let origins = std::mem::take(&mut self.origins);
+        let mut entries = merged
+            .into_iter()
+            .zip(origins)
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut headwords: HashMap<String, Vec<usize>> = HashMap::new();
+        for (pos, (word, _)) in entries.iter().flatten().enumerate() {
+            for en in word.words() {
+                headwords.entry(en.to_owned()).or_default().push(pos);
+            }
+        }
+        for word in merge_duplicates(dictionary) {
+            let mut conflicts = word
+                .words()
+                .iter()
+                .filter_map(|en| headwords.get(en))
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            conflicts.sort_unstable();
+            conflicts.dedup();
+            let pos = match (policy, conflicts.first()) {
+                (Policy::Hide, _) => {
+                    for pos in conflicts {
+                        remove_headwords(&mut entries, &mut headwords, pos, word.words());
+                    }
+                    continue;
+                }
+                (Policy::Append, Some(&first)) => {
+                    if let Some((below, origin)) = &mut entries[first] {
+                        *below = append_senses(below, &word);
+                        origin.push(layer);
+                    }
+                    first
+                }
+                (Policy::Override, Some(&first)) => {
+                    for &pos in &conflicts {
+                        remove_headwords(&mut entries, &mut headwords, pos, word.words());
+                    }
+                    let entry = Some((word.clone(), vec![layer]));
+                    if entries[first].is_none() {
+                        entries[first] = entry;
+                        first
+                    } else {
+                        entries.push(entry);
+                        entries.len() - 1
+                    }
+                }
+                (_, None) => {
+                    entries.push(Some((word.clone(), vec![layer])));
+                    entries.len() - 1
+                }
+            };
+            for en in word.words() {
+                let positions = headwords.entry(en.to_owned()).or_default();
+                if !positions.contains(&pos) {
+                    positions.push(pos);
+                }
+            }
+        }
+        let (words, origins) = entries.into_iter().flatten().unzip();
+        self.merged = Dictionary::new(words);
+        self.merged.frequencies = frequencies;
+        self.origins = origins;
+    }
+
+    /// Get the names of the layers, from the bottom.
+    pub fn layers(&self) -> &[String] {
+        self.names.as_slice()
+    }
+
+    /// Get the merged dictionary.
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.merged
+    }
+
+    /// Look up words from the merged layers.
+    pub fn look<M: Matcher>(&self, pat: &str, matcher: M) -> Option<Hit<'_>> {
+        self.merged.look(pat, matcher).map(|word| self.hit(word))
+    }
+
+    /// Get matching candidate words from the merged layers.
+    pub fn candidates<M: Matcher>(&self, pat: &str, matcher: M) -> Hits<'_> {
+        self.hits(self.merged.candidates(pat, matcher))
+    }
+
+    /// Look up words by a Japanese gloss from the merged layers.
+    pub fn reverse_look(&self, ja: &str) -> Option<Hit<'_>> {
+        self.merged.reverse_look(ja).map(|word| self.hit(word))
+    }
+
+    /// Get candidate words that have a gloss containing the Japanese query from the merged layers.
+    pub fn reverse_candidates(&self, ja: &str) -> Hits<'_> {
+        self.hits(self.merged.reverse_candidates(ja))
+    }
+
+    /// Construct the hit of a word returned by a lookup.
+    /// A word that isn't borrowed from the merged dictionary has no layers.
+    fn hit<'a>(&'a self, word: &'a Word) -> Hit<'a> {
+        match self.merged.position_of(word) {
+            Some(pos) => self.hit_at(pos),
+            None => Hit {
+                word,
+                layers: Vec::new(),
+            },
+        }
+    }
+
+    /// Construct the hit of the word at the position of the merged dictionary.
+    fn hit_at(&self, pos: usize) -> Hit<'_> {
+        let layers = self.origins[pos]
+            .iter()
+            .map(|&layer| self.names[layer].as_str())
+            .collect();
+        Hit {
+            word: &self.merged.words[pos],
+            layers,
+        }
+    }
+
+    fn hits<'a>(&'a self, candidates: Candidates<'a>) -> Hits<'a> {
+        Hits {
+            stack: self,
+            candidates,
+        }
+    }
+}
+
+/// Merge the words of a layer that share a headword into the first of them.
+fn merge_duplicates(dictionary: Dictionary) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut headwords: HashMap<String, usize> = HashMap::new();
+    for word in dictionary {
+        let pos = match word.words().iter().find_map(|en| headwords.get(en)) {
+            Some(&pos) => {
+                words[pos] = append_senses(&words[pos], &word);
+                pos
+            }
+            None => {
+                words.push(word);
+                words.len() - 1
+            }
+        };
+        for en in words[pos].words() {
+            headwords.entry(en.to_owned()).or_insert(pos);
+        }
+    }
+    words
+}
+
+/// Remove the headwords from the entry at the position, so that later entries don't hit it.
+/// The entry is removed if it has no headwords left.
+fn remove_headwords(
+    entries: &mut [Option<(Word, Vec<usize>)>],
+    headwords: &mut HashMap<String, Vec<usize>>,
+    pos: usize,
+    removed: &[String],
+) {
+    let word = match &entries[pos] {
+        Some((word, _)) => word,
+        None => return,
+    };
+    for en in word.words().iter().filter(|en| removed.contains(en)) {
+        if let Some(positions) = headwords.get_mut(en) {
+            positions.retain(|&other| other != pos);
+        }
+    }
+    let rest = word
+        .words()
+        .iter()
+        .filter(|en| !removed.contains(en))
+        .cloned()
+        .collect::<Vec<_>>();
+    if rest.is_empty() {
+        entries[pos] = None;
+    } else if let Some((word, _)) = &mut entries[pos] {
+        *word = Word::new(rest, word.mean().to_owned());
+    }
+}
+
+/// Merge the headwords and the senses of the upper word into the word below.
+fn append_senses(below: &Word, upper: &Word) -> Word {
+    let mut words = below.words().clone();
+    for en in upper.words() {
+        if !words.contains(en) {
+            words.push(en.to_owned());
+        }
+    }
+    Word::new(words, format!("{} / {}", below.mean(), upper.mean()))
+}
+
+/// A word found in a `DictionaryStack`, and the layers it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hit<'a> {
+    word: &'a Word,
+    layers: Vec<&'a str>,
+}
+
+impl<'a> Hit<'a> {
+    /// Get the word found.
+    pub fn word(&self) -> &'a Word {
+        self.word
+    }
+
+    /// Get the name of the top layer the word came from.
+    pub fn layer(&self) -> &'a str {
+        self.layers.last().copied().unwrap_or_default()
+    }
+
+    /// Get the names of all layers the word came from, from the bottom.
+    /// Several layers are reported if senses were appended.
+    pub fn layers(&self) -> &[&'a str] {
+        self.layers.as_slice()
+    }
+}
+
+/// Candidate words of a `DictionaryStack`.
+/// This struct is implemented Iterator.
+#[derive(Debug, Clone)]
+pub struct Hits<'a> {
+    stack: &'a DictionaryStack,
+    candidates: Candidates<'a>,
+}
+
+impl<'a> Iterator for Hits<'a> {
+    type Item = Hit<'a>;
+
+    fn next(&mut self) -> Option<Hit<'a>> {
+        let stack = self.stack;
+        self.candidates.next_position().map(|pos| stack.hit_at(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stack::{DictionaryStack, Policy};
+    use crate::{Dictionary, FrequencyTable, SearchMode, Word};
+
+    fn dictionary(lines: &[(&str, &str)]) -> Dictionary {
+        let words = lines
+            .iter()
+            .map(|(en, mean)| {
+                let words = en.split(',').map(str::to_owned).collect();
+                Word::new(words, mean.to_string())
+            })
+            .collect();
+        Dictionary::new(words)
+    }
+
+    fn stack() -> DictionaryStack {
+        let base = dictionary(&[
+            ("apple", "リンゴ"),
+            ("blue", "青い"),
+            ("e-mail,email", "電子メール"),
+        ]);
+        DictionaryStack::new("ejdict", base)
+    }
+
+    #[test]
+    fn test_stack_override() {
+        let mut stack = stack();
+        let user = dictionary(&[("email", "Eメール"), ("kubernetes", "コンテナ基盤")]);
+        stack.push("user", user, Policy::Override);
+        let hit = stack.look("e-mail", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "電子メール");
+        assert_eq!(hit.word().words(), &["e-mail"]);
+        let hit = stack.look("email", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "Eメール");
+        assert_eq!(hit.layer(), "user");
+        let hit = stack.look("apple", SearchMode::Exact).unwrap();
+        assert_eq!(hit.layer(), "ejdict");
+        let hit = stack.reverse_look("コンテナ").unwrap();
+        assert_eq!(hit.word().words(), &["kubernetes"]);
+        assert_eq!(stack.layers(), &["ejdict", "user"]);
+    }
+
+    #[test]
+    fn test_stack_override_twice() {
+        let mut stack = stack();
+        let user = dictionary(&[("email", "Eメール"), ("e-mail", "ハイフン付き")]);
+        stack.push("user", user, Policy::Override);
+        let hit = stack.look("email", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "Eメール");
+        let hit = stack.look("e-mail", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "ハイフン付き");
+        assert_eq!(stack.dictionary().iter().count(), 4);
+    }
+
+    #[test]
+    fn test_stack_duplicates_in_layer() {
+        let mut stack = stack();
+        let user = dictionary(&[("apple", "アップル社"), ("apple", "リンゴ味")]);
+        stack.push("user", user, Policy::Override);
+        let hit = stack.look("apple", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "アップル社 / リンゴ味");
+        assert_eq!(stack.dictionary().iter().count(), 3);
+    }
+
+    #[test]
+    fn test_stack_hide_from_reader() {
+        let mut stack = stack();
+        let hidden = Dictionary::from_headword_reader("blue\nemail\n".as_bytes()).unwrap();
+        stack.push("hidden", hidden, Policy::Hide);
+        assert_eq!(stack.look("blue", SearchMode::Exact), None);
+        assert_eq!(stack.look("email", SearchMode::Exact), None);
+        let hit = stack.look("e-mail", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "電子メール");
+    }
+
+    #[test]
+    fn test_stack_keeps_frequencies() {
+        let mut base = dictionary(&[("apple", "リンゴ")]);
+        let mut table = FrequencyTable::new();
+        table.insert("apple", 120);
+        base.set_frequency_table(table);
+        let mut stack = DictionaryStack::new("ejdict", base);
+        stack.push("user", dictionary(&[("blue", "青い")]), Policy::Override);
+        let apple = stack.look("apple", SearchMode::Exact).unwrap().word();
+        assert_eq!(stack.dictionary().frequency(apple), Some(120));
+    }
+
+    #[test]
+    fn test_stack_append_and_hide() {
+        let mut stack = stack();
+        stack.push(
+            "terms",
+            dictionary(&[("apple", "アップル社")]),
+            Policy::Append,
+        );
+        stack.push("hidden", dictionary(&[("blue", "")]), Policy::Hide);
+        let hit = stack.look("apple", SearchMode::Exact).unwrap();
+        assert_eq!(hit.word().mean(), "リンゴ / アップル社");
+        assert_eq!(hit.layers(), &["ejdict", "terms"]);
+        assert_eq!(stack.look("blue", SearchMode::Exact), None);
+        let layers = stack
+            .candidates("", SearchMode::Fuzzy)
+            .map(|hit| hit.layer())
+            .collect::<Vec<_>>();
+        assert_eq!(layers, vec!["terms", "ejdict"]);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::Word;
+
+/// Suffix array over all headwords.
+/// Every suffix of every headword is sorted,
+/// so the headwords containing a pattern are a contiguous range.
+#[derive(Debug, Default)]
+pub(crate) struct SuffixArray {
+    keys: Vec<(String, usize)>,
+    suffixes: Vec<(u32, u32)>,
+}
+
+impl SuffixArray {
+    /// Build the suffix array from all headwords of the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let keys = words
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, word)| word.words().iter().map(move |en| (en.to_owned(), pos)))
+            .collect::<Vec<_>>();
+        let mut suffixes = keys
+            .iter()
+            .enumerate()
+            .flat_map(|(key, (en, _))| {
+                en.char_indices()
+                    .map(move |(offset, _)| (key as u32, offset as u32))
+            })
+            .collect::<Vec<_>>();
+        let mut array = SuffixArray {
+            keys,
+            suffixes: Vec::new(),
+        };
+        suffixes.sort_by(|a, b| array.suffix(*a).cmp(array.suffix(*b)));
+        array.suffixes = suffixes;
+        array
+    }
+
+    fn suffix(&self, (key, offset): (u32, u32)) -> &str {
+        &self.keys[key as usize].0[offset as usize..]
+    }
+
+    /// Get the positions of the words that have a headword containing the pattern, in file order.
+    pub(crate) fn search(&self, pat: &str) -> Vec<usize> {
+        let start = self
+            .suffixes
+            .partition_point(|&suffix| self.suffix(suffix) < pat);
+        let mut positions = self.suffixes[start..]
+            .iter()
+            .take_while(|&&suffix| self.suffix(suffix).starts_with(pat))
+            .map(|&(key, _)| self.keys[key as usize].1)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::suffix::SuffixArray;
+    use crate::Word;
+
+    #[test]
+    fn test_suffix_array_search() {
+        let words = vec![
+            Word::new(vec!["photograph".to_string()], "".to_string()),
+            Word::new(
+                vec!["graphic".to_string(), "graphical".to_string()],
+                "".to_string(),
+            ),
+            Word::new(vec!["station".to_string()], "".to_string()),
+        ];
+        let array = SuffixArray::build(&words);
+        assert_eq!(array.search("graph"), vec![0, 1]);
+        assert_eq!(array.search("tion"), vec![2]);
+        assert_eq!(array.search("a"), vec![0, 1, 2]);
+        assert!(array.search("xyz").is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::Word;
+use failure::Fail;
+use std::fmt;
+
+/// An error returned when a line of ejdict TSV can't be parsed.
+/// `line` and `column` are 1-based, and `column` counts characters.
+#[derive(Clone, Debug, Fail, PartialEq, Eq)]
+#[fail(display = "line {}, column {}: {}", line, column, reason)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    reason: ParseReason,
+}
+
+impl ParseError {
+    fn new(line: usize, column: usize, reason: ParseReason) -> Self {
+        ParseError {
+            line,
+            column,
+            reason,
+        }
+    }
+
+    /// Get the line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the column number.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get why the line can't be parsed.
+    pub fn reason(&self) -> &ParseReason {
+        &self.reason
+    }
+}
+
+/// Why a line of ejdict TSV can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseReason {
+    /// The line has no tab between the headwords and the mean.
+    MissingTab,
+    /// A headword in the comma-separated list is empty.
+    EmptyHeadword,
+    /// The mean after the tab is empty.
+    EmptyMean,
+}
+
+impl fmt::Display for ParseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseReason::MissingTab => write!(f, "missing tab between headwords and mean"),
+            ParseReason::EmptyHeadword => write!(f, "empty headword"),
+            ParseReason::EmptyMean => write!(f, "empty mean"),
+        }
+    }
+}
+
+/// Parse a line of ejdict TSV, which is comma-separated headwords and the mean separated by a tab.
+/// `number` is the line number reported by errors.
+pub(crate) fn parse_line(line: &str, number: usize) -> Result<Word, ParseError> {
+    let line = trim_line(line);
+    let (words, mean) = match line.split_once('\t') {
+        Some(secs) => secs,
+        None => {
+            let column = line.chars().count() + 1;
+            return Err(ParseError::new(number, column, ParseReason::MissingTab));
+        }
+    };
+    let headwords = parse_headwords(words, number)?;
+    if mean.trim().is_empty() {
+        let column = words.chars().count() + 2;
+        return Err(ParseError::new(number, column, ParseReason::EmptyMean));
+    }
+    Ok(Word::new(headwords, mean.to_owned()))
+}
+
+/// Parse a line of ejdict TSV like `parse_line`, but accept empty headwords and an empty mean.
+/// A line without a tab is headwords with an empty mean.
+pub(crate) fn parse_line_lenient(line: &str) -> Word {
+    let line = trim_line(line);
+    let (words, mean) = line.split_once('\t').unwrap_or((line, ""));
+    let headwords = words.split(',').map(str::to_owned).collect();
+    Word::new(headwords, mean.to_owned())
+}
+
+/// Remove the line break at the end of the line.
+fn trim_line(line: &str) -> &str {
+    line.trim_end_matches(['\r', '\n'])
+}
+
+/// Parse the comma-separated headwords at the start of a line.
+/// `number` is the line number reported by errors.
+pub(crate) fn parse_headwords(words: &str, number: usize) -> Result<Vec<String>, ParseError> {
+    let mut column = 1;
+    let mut headwords = Vec::new();
+    for en in words.split(',') {
+        if en.trim().is_empty() {
+            return Err(ParseError::new(number, column, ParseReason::EmptyHeadword));
+        }
+        headwords.push(en.to_owned());
+        column += en.chars().count() + 1;
+    }
+    Ok(headwords)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tsv::{parse_line, ParseReason};
+    use crate::Word;
+
+    #[test]
+    fn test_parse_line() {
+        let word = parse_line("color,colour\t色\r\n", 1).unwrap();
+        assert_eq!(
+            word,
+            Word::new(
+                vec!["color".to_string(), "colour".to_string()],
+                "色".to_string()
+            )
+        );
+        let err = parse_line("apple リンゴ", 3).unwrap_err();
+        assert_eq!((err.line(), err.column()), (3, 10));
+        assert_eq!(err.reason(), &ParseReason::MissingTab);
+        let err = parse_line("color,,colour\t色", 4).unwrap_err();
+        assert_eq!((err.line(), err.column()), (4, 7));
+        assert_eq!(err.reason(), &ParseReason::EmptyHeadword);
+        let err = parse_line("apple\t", 5).unwrap_err();
+        assert_eq!((err.line(), err.column()), (5, 7));
+        assert_eq!(err.reason(), &ParseReason::EmptyMean);
+        assert_eq!(err.to_string(), "line 5, column 7: empty mean".to_string());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Get the Damerau-Levenshtein distance between two strings.
+/// Insertion, deletion, substitution and transposition of adjacent characters cost 1 each.
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let max = a.len() + b.len();
+    let width = b.len() + 2;
+    let mut d = vec![0; (a.len() + 2) * width];
+    d[0] = max;
+    for i in 0..=a.len() {
+        d[(i + 1) * width] = max;
+        d[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        d[j + 1] = max;
+        d[width + j + 1] = j;
+    }
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=a.len() {
+        let mut last_col = 0;
+        for j in 1..=b.len() {
+            let i1 = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let j1 = last_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_col = j;
+                0
+            } else {
+                1
+            };
+            let substitution = d[i * width + j] + cost;
+            let insertion = d[(i + 1) * width + j] + 1;
+            let deletion = d[i * width + j + 1] + 1;
+            let transposition = d[i1 * width + j1] + (i - i1 - 1) + 1 + (j - j1 - 1);
+            d[(i + 1) * width + j + 1] =
+                substitution.min(insertion).min(deletion).min(transposition);
+        }
+        last_row.insert(a[i - 1], i);
+    }
+    d[(a.len() + 1) * width + b.len() + 1]
+}
+
+/// BK-tree over keys of the words of a dictionary, such as lowercased headwords.
+/// Damerau-Levenshtein distance is a metric, so subtrees that are too far are skipped.
+#[derive(Debug, Default)]
+pub(crate) struct BkTree {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+struct Node {
+    key: String,
+    positions: Vec<usize>,
+    children: Vec<(usize, usize)>,
+}
+
+impl BkTree {
+    /// Build the tree from all lowercased headwords of the words.
+    pub(crate) fn build(words: &[Word]) -> Self {
+        let keys = words
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, word)| word.words().iter().map(move |en| (en.to_lowercase(), pos)));
+        BkTree::from_keys(keys)
+    }
+
+    /// Build the tree from pairs of a key and the position of the word.
+    pub(crate) fn from_keys<I>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = (String, usize)>,
+    {
+        let mut tree = BkTree::default();
+        for (key, pos) in keys {
+            tree.insert(key, pos);
+        }
+        tree
+    }
+
+    fn insert(&mut self, key: String, pos: usize) {
+        if self.nodes.is_empty() {
+            self.push(key, pos);
+            return;
+        }
+        let mut current = 0;
+        loop {
+            let dist = damerau_levenshtein(&key, &self.nodes[current].key);
+            if dist == 0 {
+                let positions = &mut self.nodes[current].positions;
+                if !positions.contains(&pos) {
+                    positions.push(pos);
+                }
+                return;
+            }
+            let child = self.nodes[current]
+                .children
+                .iter()
+                .find(|(d, _)| *d == dist)
+                .map(|(_, child)| *child);
+            match child {
+                Some(child) => current = child,
+                None => {
+                    let child = self.push(key, pos);
+                    self.nodes[current].children.push((dist, child));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, key: String, pos: usize) -> usize {
+        self.nodes.push(Node {
+            key,
+            positions: vec![pos],
+            children: Vec::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Get the positions of the words within the distance from the key.
+    /// Positions are ordered by distance, then by headword, without duplicates.
+    pub(crate) fn search(&self, pat: &str, max_distance: usize) -> Vec<(usize, usize)> {
+        let mut found: Vec<(usize, &str, usize)> = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+            let dist = damerau_levenshtein(pat, &node.key);
+            if dist <= max_distance {
+                found.extend(
+                    node.positions
+                        .iter()
+                        .map(|&pos| (dist, node.key.as_str(), pos)),
+                );
+            }
+            for &(d, child) in &node.children {
+                if d + max_distance >= dist && d <= dist + max_distance {
+                    stack.push(child);
+                }
+            }
+        }
+        found.sort();
+        let mut seen = HashSet::new();
+        found
+            .into_iter()
+            .filter(|&(_, _, pos)| seen.insert(pos))
+            .map(|(dist, _, pos)| (pos, dist))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::typo::{damerau_levenshtein, BkTree};
+    use crate::Word;
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein("apple", "apple"), 0);
+        assert_eq!(damerau_levenshtein("aplpe", "apple"), 1);
+        assert_eq!(damerau_levenshtein("aple", "apple"), 1);
+        assert_eq!(damerau_levenshtein("apples", "apple"), 1);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 2);
+        assert_eq!(damerau_levenshtein("", "abc"), 3);
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_bk_tree_search() {
+        let words = vec![
+            Word::new(vec!["apply".to_string()], "".to_string()),
+            Word::new(vec!["apple".to_string()], "".to_string()),
+            Word::new(vec!["Maple".to_string()], "".to_string()),
+            Word::new(vec!["blue".to_string()], "".to_string()),
+        ];
+        let tree = BkTree::build(&words);
+        assert_eq!(tree.search("aplpe", 1), vec![(1, 1)]);
+        assert_eq!(tree.search("aplpe", 2), vec![(1, 1), (0, 2), (2, 2)]);
+        assert!(tree.search("zzzzz", 2).is_empty());
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "regex")]
+use ejdict_rs_core::RegexError;
+use ejdict_rs_core::{LoadError, QueryError};
 use failure::{Backtrace, Context, Fail};
 use serde_json::Error as SerdeError;
 use std::env::VarError;
@@ -46,8 +49,21 @@ pub enum ErrorKind {
     #[fail(display = "serde error")]
     Serde(SerdeError),
 
+    #[fail(display = "failed to load the dictionary: {}", _0)]
+    Load(LoadError),
+
     #[fail(display = "not found from English-Japanese Dictionary: {}", en)]
     NotFound { en: String },
+
+    #[fail(display = "not found from Japanese meanings: {}", ja)]
+    ReverseNotFound { ja: String },
+
+    #[fail(display = "invalid query: {}", _0)]
+    Query(QueryError),
+
+    #[cfg(feature = "regex")]
+    #[fail(display = "invalid regex: {}", _0)]
+    Regex(RegexError),
 }
 
 impl From<VarError> for ErrorKind {
@@ -62,6 +78,22 @@ impl From<SerdeError> for ErrorKind {
     }
 }
 
+impl From<LoadError> for ErrorKind {
+    fn from(err: LoadError) -> Self {
+        ErrorKind::Load(err)
+    }
+}
+
+impl From<QueryError> for ErrorKind {
+    fn from(err: QueryError) -> Self {
+        match err {
+            #[cfg(feature = "regex")]
+            QueryError::Regex(err) => ErrorKind::Regex(err),
+            err => ErrorKind::Query(err),
+        }
+    }
+}
+
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Error {
         Error {
@@ -90,5 +122,19 @@ impl From<SerdeError> for Error {
     }
 }
 
+impl From<LoadError> for Error {
+    fn from(err: LoadError) -> Self {
+        let kind = ErrorKind::from(err);
+        Error::from(kind)
+    }
+}
+
+impl From<QueryError> for Error {
+    fn from(err: QueryError) -> Self {
+        let kind = ErrorKind::from(err);
+        Error::from(kind)
+    }
+}
+
 /// Short hand for Result type
 pub type Result<T> = std::result::Result<T, Error>;
@@ -43,6 +43,15 @@
 //! # }
 //! ```
 //!
+//! ## Dictionary data
+//!
+//! The dictionary embedded at build time is used by default.
+//! To use another release of ejdict without recompiling, set `EJDICT_DATA_PATH`
+//! to an ejdict TSV file such as `ejdic-hand-utf8.txt`, or to a JSON file serialized from `Dictionary`.
+//! The file is loaded on the first lookup.
+//! If it can't be loaded, the functions of this crate return `ErrorKind::Load`,
+//! and the file is loaded again on the next lookup.
+//!
 //! ## Install
 //!
 //! Write the following contents in Cargo.toml.
@@ -63,16 +72,19 @@
 //!
 //! https://doc.rust-lang.org/cargo/reference/specifying-dependencies.html#specifying-dependencies-from-git-repositories
 //!
+//! The `regex` feature enables `SearchMode::Regex`, which searches headwords or meanings by a regular expression.
+//!
+//! ```toml
+//! [dependencies]
+//! ejdict_rs = { version = "0.0.4", features = ["regex"] }
+//! ```
+//!
 //! ## Dependencies
 //!
 //! - crates
 //!   - failure
 //!     - Apache 2.0, MIT
 //!     - Error management
-//!   - lazy_static
-//!     - Apache 2.0, MIT
-//!     - Copyright (c) 2010 The Rust Project Developers
-//!     - A small macro for defining lazy evaluated static variables in Rust.
 //!   - serde_json
 //!     - Apache 2.0, MIT
 //!     - Strongly typed JSON library.
@@ -93,20 +105,29 @@
 //! [tomo3110](https://github.com/tomo3110)
 //!
 
-use lazy_static::lazy_static;
 use std::env;
+use std::sync::OnceLock;
 
 mod errors;
 
-pub use ejdict_rs_core::{Dictionary, SearchMode, Word};
+pub use ejdict_rs_core::{
+    fold_en, metaphone, normalize_ja, spelling_variants, Candidates, Dictionary, DictionaryStack,
+    Field, FrequencyError, FrequencyTable, Gloss, Hit, Hits, Inflection, Label, Lemma, LoadError,
+    Lookup, MatchKind, Matcher, ParseError, ParseReason, Pattern, Policy, QueryError, Ranked,
+    Redirect, RedirectKind, Region, Register, Resolved, Score, SearchMode, Sense, Variant, Word,
+    Words,
+};
+#[cfg(feature = "regex")]
+pub use ejdict_rs_core::{RegexError, RegexTarget};
 pub use errors::{Error, ErrorKind, Result};
 
-/// List of candidates that can be obtained as search results
-pub type Candidates<T> = ejdict_rs_core::Candidates<std::vec::IntoIter<T>>;
+/// Environment variable with the path of the dictionary data used instead of the embedded one.
+const EJDICT_DATA_PATH: &str = "EJDICT_DATA_PATH";
 
-lazy_static! {
-    static ref EJDICT_DISCIONARY: Dictionary = load_dictionary().unwrap();
-}
+/// Maximum number of suggestions returned by `suggest`.
+const MAX_SUGGESTIONS: usize = 5;
+
+static EJDICT_DISCIONARY: OnceLock<Dictionary> = OnceLock::new();
 
 #[cfg(windows)]
 fn get_ejdict_json<'a>() -> &'a str {
@@ -118,13 +139,74 @@ fn get_ejdict_json<'a>() -> &'a str {
     include_str!(concat!(env!("OUT_DIR"), "/ejdict.json"))
 }
 
-fn load_dictionary() -> Result<Dictionary> {
+/// Load a new copy of the dictionary that the functions of this crate use,
+/// from `EJDICT_DATA_PATH` if it is set, or from the embedded data.
+/// Use it as the bottom layer of a `DictionaryStack`.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs::{Dictionary, DictionaryStack, Policy, SearchMode};
+///
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let user = Dictionary::new(vec![]);
+/// let mut stack = DictionaryStack::new("ejdict", ejdict_rs::load_dictionary()?);
+/// stack.push("user", user, Policy::Override);
+/// let hit = stack.look("apple", SearchMode::Exact).unwrap();
+/// assert_eq!(hit.layer(), "ejdict");
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn load_dictionary() -> Result<Dictionary> {
+    match env::var(EJDICT_DATA_PATH) {
+        Ok(path) => return Ok(Dictionary::from_path(path)?),
+        Err(env::VarError::NotPresent) => {}
+        Err(err) => return Err(Error::from(err)),
+    }
     let src = get_ejdict_json();
     let dict = serde_json::from_str::<Dictionary>(src)?;
     Ok(dict)
 }
 
+fn not_found(word: &str) -> Error {
+    let kind = ErrorKind::NotFound {
+        en: word.to_owned(),
+    };
+    Error::from(kind)
+}
+
+/// Get the dictionary that the functions of this crate use, loading it on the first call.
+/// If it can't be loaded, `ErrorKind::Load` is returned and it is loaded again on the next call.
+/// Pass it where a `Lookup` is taken, and swap in another backend in tests.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs::{Lookup, SearchMode};
+///
+/// fn mean_of(dict: &dyn Lookup, en: &str) -> Option<String> {
+///     dict.look(en, SearchMode::Exact).map(|word| word.mean().to_owned())
+/// }
+///
+/// # fn main() -> ejdict_rs::Result<()> {
+/// assert_eq!(mean_of(ejdict_rs::dictionary()?, "apple"), Some("『リンゴ』;リンゴの木".to_string()));
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn dictionary() -> Result<&'static Dictionary> {
+    if let Some(dict) = EJDICT_DISCIONARY.get() {
+        return Ok(dict);
+    }
+    let dict = load_dictionary()?;
+    Ok(EJDICT_DISCIONARY.get_or_init(|| dict))
+}
+
 /// Look up words from an English-Japanese Dictionary.
+/// If the word isn't found, `ErrorKind::NotFound` is returned. Call `suggest` for nearby headwords.
+/// If the query isn't valid in the mode, such as a malformed pattern, `ErrorKind::Query` is returned.
+/// With the `regex` feature, a regular expression that fails to compile is `ErrorKind::Regex`.
 ///
 /// # Example
 ///
@@ -142,16 +224,74 @@ fn load_dictionary() -> Result<Dictionary> {
 /// ```
 ///
 pub fn look(word: &str, mode: SearchMode) -> Result<&Word> {
-    let ref dict: Dictionary = *EJDICT_DISCIONARY;
-    dict.look(word, mode).ok_or_else(|| {
-        let kind = ErrorKind::NotFound {
-            en: word.to_owned(),
-        };
-        Error::from(kind)
-    })
+    let dict = dictionary()?;
+    dict.try_look(word, mode)?.ok_or_else(|| not_found(word))
+}
+
+/// Look up words, and report the British or American spelling variant that was used, if any.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs::SearchMode;
+///
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let variant = ejdict_rs::look_variant("apple", SearchMode::Exact)?;
+/// assert_eq!(variant.spelling(), None);
+/// assert_eq!(variant.word().mean(), "『リンゴ』;リンゴの木");
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn look_variant(word: &str, mode: SearchMode) -> Result<Variant<'static>> {
+    let dict = dictionary()?;
+    mode.validate(word)?;
+    dict.look_variant(word, mode).ok_or_else(|| not_found(word))
+}
+
+/// Look up words by the lemma of an inflected form, and report which lemma matched.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let lemma = ejdict_rs::look_lemma("apples")?;
+/// assert_eq!(lemma.lemma(), "apple");
+/// assert_eq!(lemma.word().mean(), "『リンゴ』;リンゴの木");
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn look_lemma(word: &str) -> Result<Lemma<'static>> {
+    let dict = dictionary()?;
+    dict.look_lemma(word).ok_or_else(|| not_found(word))
+}
+
+/// Look up words and follow the redirect if the word is a stub entry such as `=color`.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs::SearchMode;
+///
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let resolved = ejdict_rs::look_resolved("apple", SearchMode::Exact)?;
+/// assert_eq!(resolved.stub(), None);
+/// assert_eq!(resolved.entry().mean(), "『リンゴ』;リンゴの木");
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn look_resolved(word: &str, mode: SearchMode) -> Result<Resolved<'static>> {
+    let dict = dictionary()?;
+    mode.validate(word)?;
+    dict.look_resolved(word, mode)
+        .ok_or_else(|| not_found(word))
 }
 
 /// Get matching candidate words.
+/// The candidates borrow the embedded dictionary, which is loaded only once.
+/// In `SearchMode::Pattern`, `?`, `*` and character classes such as `[aeiou]` can be used.
 ///
 /// # Example
 ///
@@ -163,11 +303,95 @@ pub fn look(word: &str, mode: SearchMode) -> Result<&Word> {
 /// for word in candidates {
 ///     // something ...
 /// }
+/// let mut five_letters = ejdict_rs::candidates("a???e", SearchMode::Pattern)?;
+/// assert!(five_letters.any(|word| word.words().contains(&"apple".to_string())));
 /// # Ok(())
 /// # }
 /// ```
 ///
-pub fn candidates(word: &str, mode: SearchMode) -> Result<Candidates<Word>> {
-    let dict = load_dictionary()?;
-    Ok(dict.candidates(word, mode))
+pub fn candidates(word: &str, mode: SearchMode) -> Result<Candidates<'static>> {
+    let dict = dictionary()?;
+    let candidates = dict.try_candidates(word, mode)?;
+    Ok(candidates)
+}
+
+/// Get matching candidate words with their scores, the most relevant first.
+/// The score tells how the headword matched, so the order can be inspected.
+///
+/// # Example
+///
+/// ```
+/// use ejdict_rs::{MatchKind, SearchMode};
+///
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let mut ranked = ejdict_rs::ranked_candidates("apple", SearchMode::Fuzzy)?;
+/// let (word, score) = ranked.next().unwrap();
+/// assert_eq!(word.words(), &["apple"]);
+/// assert_eq!(score.kind(), MatchKind::Exact);
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn ranked_candidates(word: &str, mode: SearchMode) -> Result<Ranked<'static>> {
+    let dict = dictionary()?;
+    mode.validate(word)?;
+    Ok(dict.ranked(word, mode))
+}
+
+/// Get headwords near the word, to suggest after `look` returns `ErrorKind::NotFound`.
+/// This searches for typos, so call it only when the suggestions are shown.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let suggestions = ejdict_rs::suggest("aple")?;
+/// assert!(suggestions.contains(&"apple".to_string()));
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn suggest(word: &str) -> Result<Vec<String>> {
+    let dict = dictionary()?;
+    Ok(dict.suggest(word, MAX_SUGGESTIONS))
+}
+
+/// Look up words by a Japanese meaning.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let word = ejdict_rs::reverse_look("リンゴ")?;
+/// assert_eq!(word.words(), &["apple"]);
+/// #   Ok(())
+/// # }
+/// ```
+///
+pub fn reverse_look(ja: &str) -> Result<&'static Word> {
+    let dict = dictionary()?;
+    dict.reverse_look(ja).ok_or_else(|| {
+        let kind = ErrorKind::ReverseNotFound { ja: ja.to_owned() };
+        Error::from(kind)
+    })
+}
+
+/// Get candidate words whose Japanese meaning contains the query.
+/// Words whose primary meaning is the query come first.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> ejdict_rs::Result<()> {
+/// let candidates = ejdict_rs::reverse_candidates("リンゴ")?;
+/// for word in candidates {
+///     // something ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn reverse_candidates(ja: &str) -> Result<Candidates<'static>> {
+    let dict = dictionary()?;
+    Ok(dict.reverse_candidates(ja))
 }